@@ -1,5 +1,17 @@
-use odawg::{from_word, FlatDawg, ReadDawg, WideNode};
-use std::{fs, path::Path};
+use odawg::{from_word, FlatDawg, PackedDawg, ReadDawg, ThinNode, WideNode};
+use std::{fs, path::Path, time::Instant};
+
+/// Times how long it takes to look up every word in `words`
+fn bench(name: &str, dawg: &impl ReadDawg, words: &[Vec<u8>]) {
+    let start = Instant::now();
+    let found = words.iter().filter(|word| dawg.has(*word)).count();
+    let elapsed = start.elapsed();
+    println!(
+        "{name}: found {found} words in {:.2}ms ({:.0}ns per word)",
+        elapsed.as_secs_f64() * 1_000.0,
+        elapsed.as_nanos() as f64 / words.len() as f64
+    );
+}
 
 fn main() {
     let file_path = Path::new(file!()).parent().unwrap();
@@ -9,7 +21,8 @@ fn main() {
     let content =
         fs::read_to_string(&words_path).expect("Should be able to read words file as text");
 
-    let mut dawg: FlatDawg<WideNode> = content.split("\n").map(from_word).collect();
+    let words: Vec<_> = content.split("\n").map(from_word).collect();
+    let mut dawg: FlatDawg<WideNode> = words.iter().collect();
 
     println!("Dawg size = {}", dawg.len());
     println!("Dawg has {} nodes", dawg.0.len());
@@ -38,5 +51,26 @@ fn main() {
             .expect("Should be able to access path")
             .len() as f64
             / 1_000_000.0
-    )
+    );
+
+    // Compact layouts need contiguous children, so we use the trimmed trie
+    let mut trie: FlatDawg<WideNode> = words.iter().collect();
+    trie.trim();
+    let thin: FlatDawg<ThinNode> = trie.try_into().expect("tries are always contiguous");
+    let packed = PackedDawg::from(&thin);
+
+    println!("Compact dawg has {} nodes", thin.0.len());
+    println!(
+        "Thin nodes: {:.2}MB ({} bits per node)",
+        (thin.0.len() * size_of::<ThinNode>()) as f64 / 1_000_000.0,
+        size_of::<ThinNode>() * 8
+    );
+    println!(
+        "Packed nodes: {:.2}MB ({} bits per node)",
+        packed.byte_len() as f64 / 1_000_000.0,
+        packed.node_bits()
+    );
+
+    bench("Thin lookup", &thin, &words);
+    bench("Packed lookup", &packed, &words);
 }
@@ -1,10 +1,27 @@
-use super::{IndexDawg, ReadDawg};
+use super::{IndexDawg, NumberedDawg, ReadDawg};
 use crate::node::AllNode;
 use std::array;
 
 /// A DAWG that only has words present in all contained DAWGs
+///
+/// ```
+/// # use odawg::{from_word, AllDawg, FlatDawg, ReadDawg};
+/// let dawg0: FlatDawg = ["cat", "cats", "dog"].map(from_word).into_iter().collect();
+/// let dawg1: FlatDawg = ["cat", "dog"].map(from_word).into_iter().collect();
+///
+/// let all = AllDawg::new([dawg0, dawg1]);
+/// assert_eq!(all.len(), 2);
+/// assert!(!all.has("cats"));
+/// ```
 pub struct AllDawg<const N: usize, D>([D; N]);
 
+impl<const N: usize, D> AllDawg<N, D> {
+    /// Creates a view of the intersection of `dawgs`
+    pub fn new(dawgs: [D; N]) -> Self {
+        Self(dawgs)
+    }
+}
+
 impl<const N: usize, D: IndexDawg<Idx = usize>> IndexDawg for AllDawg<N, D> {
     type Idx = [D::Idx; N];
     const ROOT_IDX: Self::Idx = [0; N];
@@ -18,4 +35,78 @@ impl<const N: usize, D: IndexDawg<Idx = usize>> IndexDawg for AllDawg<N, D> {
     }
 }
 
-impl<const N: usize, D: IndexDawg<Idx = usize>> ReadDawg for AllDawg<N, D> {}
+impl<const N: usize, D: IndexDawg<Idx = usize>> ReadDawg for AllDawg<N, D> {
+    /// Counts the words below each combination of indices reached,<br>
+    /// so each combination is only searched once.
+    fn len(&self) -> usize {
+        NumberedDawg::new(self).len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AllDawg;
+    use crate::{from_word, into_word, utils::fixtures::binary_words, FlatDawg, ReadDawg};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn len(words0 in vec(vec(0..4u8, 0..8), 0..50), words1 in vec(vec(0..4u8, 0..8), 0..50)) {
+        let dawg0: FlatDawg = words0.iter().collect();
+        let dawg1: FlatDawg = words1.iter().collect();
+        let dawg = AllDawg([dawg0, dawg1]);
+        assert_eq!(dawg.len(), dawg.words().count());
+        assert_eq!(dawg.is_empty(), dawg.words().next().is_none());
+      }
+    }
+
+    #[test]
+    fn fuzzy() {
+        let dawg0: FlatDawg = ["cat", "cart", "coat", "dog"]
+            .map(from_word)
+            .into_iter()
+            .collect();
+        let dawg1: FlatDawg = ["cat", "coat", "cot"].map(from_word).into_iter().collect();
+        let dawg = AllDawg([dawg0, dawg1]);
+
+        let found: Vec<_> = dawg
+            .fuzzy("cot", 1)
+            .map(|(word, dist)| (into_word(word), dist))
+            .collect();
+        assert_eq!(found, [("cat".to_string(), 1), ("coat".to_string(), 1)]);
+    }
+
+    #[test]
+    fn len_counts_nodes_once() {
+        let dawg = AllDawg([binary_words(40), binary_words(40)]);
+        assert_eq!(dawg.len(), 1 << 40);
+        let dawg = AllDawg([binary_words(40), binary_words(39)]);
+        assert!(dawg.is_empty());
+    }
+
+    #[test]
+    fn words_with_prefix() {
+        let dawg0: FlatDawg = ["cat", "cats", "cab", "dog"]
+            .map(from_word)
+            .into_iter()
+            .collect();
+        let dawg1: FlatDawg = ["cat", "cab", "cabs", "cow"]
+            .map(from_word)
+            .into_iter()
+            .collect();
+        let dawg = AllDawg([dawg0, dawg1]);
+
+        let mut words: Vec<_> = dawg.words_with_prefix("ca").map(into_word).collect();
+        words.sort();
+        assert_eq!(words, ["cab", "cat"]);
+        assert!(dawg.has_prefix("cat"));
+        assert!(!dawg.has_prefix("cats"));
+        assert!(!dawg.has_prefix("co"));
+        assert!(!dawg.has_prefix("d"));
+    }
+}
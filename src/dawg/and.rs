@@ -0,0 +1,108 @@
+use super::{IndexDawg, ReadDawg};
+use crate::node::AndNode;
+
+/// A DAWG that only has words present in both contained DAWGs,<br>
+/// which can be of different types, i.e. a DAWG and a [`Pattern`](crate::Pattern).
+///
+/// More DAWGs can be combined by nesting, i.e. `And(a, And(b, c))`.<br>
+/// Children are found from the first DAWG, so it should be the sparser one.
+///
+/// ```
+/// # use odawg::{from_word, And, FlatDawg, ReadDawg, ThinNode, WideNode};
+/// let wide: FlatDawg<WideNode> = ["cat", "cats", "dog"].map(from_word).into_iter().collect();
+/// let mut thin: FlatDawg<WideNode> = ["cat", "dog", "dogs"].map(from_word).into_iter().collect();
+/// thin.trim();
+/// let thin: FlatDawg<ThinNode> = thin.try_into().unwrap();
+///
+/// let both = And(&wide, &thin);
+/// assert_eq!(both.len(), 2);
+/// assert!(both.has("cat"));
+/// assert!(!both.has("cats"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<A: IndexDawg, B: IndexDawg> IndexDawg for And<A, B> {
+    type Idx = (A::Idx, B::Idx);
+    const ROOT_IDX: Self::Idx = (A::ROOT_IDX, B::ROOT_IDX);
+
+    type NodeRef<'a>
+        = AndNode<A::NodeRef<'a>, B::NodeRef<'a>>
+    where
+        Self: 'a;
+    fn index(&self, (idx0, idx1): Self::Idx) -> Self::NodeRef<'_> {
+        AndNode(self.0.index(idx0), self.1.index(idx1))
+    }
+}
+
+impl<A: IndexDawg, B: IndexDawg> ReadDawg for And<A, B> {}
+
+#[cfg(test)]
+mod test {
+    use super::And;
+    use crate::{
+        pattern::Pattern,
+        utils::{
+            convert::from_word,
+            fixtures::{sorted_words, thin_dawg},
+        },
+        FlatDawg, ReadDawg, WriteDawg,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn matches_intersect(
+        words0 in vec(vec(0..4u8, 0..6), 0..50),
+        words1 in vec(vec(0..4u8, 0..6), 0..50),
+      ) {
+        let wide: FlatDawg = words0.iter().collect();
+        let thin = thin_dawg(&words1);
+
+        let mut expected = wide.clone();
+        expected.intersect(&thin);
+        assert_eq!(sorted_words(&And(&wide, &thin)), sorted_words(&expected));
+        assert_eq!(sorted_words(&And(&thin, &wide)), sorted_words(&expected));
+      }
+
+      #[test]
+      fn matches_pattern(
+        words in vec(vec(0..4u8, 0..6), 0..50),
+        masks in vec(1..16u32, 0..6),
+      ) {
+        let dawg: FlatDawg = words.iter().collect();
+        let pattern = Pattern(masks);
+
+        let mut found: Vec<_> = dawg.matching(&pattern).collect();
+        found.sort();
+
+        let mut expected = dawg.clone();
+        expected.keep(|word| pattern.has(word));
+        assert_eq!(found, sorted_words(&expected));
+      }
+    }
+
+    #[test]
+    fn board_constraint() {
+        let dawg: FlatDawg = ["cat", "cot", "cut", "cats", "bat", "at"]
+            .map(from_word)
+            .into_iter()
+            .collect();
+        let pattern: Pattern = "c[ao]t".parse().unwrap();
+
+        let mut found: Vec<_> = dawg.matching(&pattern).collect();
+        found.sort();
+        assert_eq!(found, [from_word("cat"), from_word("cot")]);
+    }
+
+    #[test]
+    fn nested() {
+        let dawg0: FlatDawg = ["cat", "cot", "cut"].map(from_word).into_iter().collect();
+        let dawg1: FlatDawg = ["cat", "cot", "dog"].map(from_word).into_iter().collect();
+        let pattern: Pattern = "-a-".parse().unwrap();
+
+        let dawg = And(&dawg0, And(&dawg1, &pattern));
+        assert_eq!(sorted_words(&dawg), [from_word("cat")]);
+    }
+}
@@ -3,19 +3,84 @@ use crate::node::AnyNode;
 use std::array;
 
 /// A DAWG that has words present in any contained DAWGs
+///
+/// ```
+/// # use odawg::{from_word, AnyDawg, FlatDawg, ReadDawg};
+/// let dawg0: FlatDawg = ["cat", "cats"].map(from_word).into_iter().collect();
+/// let dawg1: FlatDawg = ["cat", "dog"].map(from_word).into_iter().collect();
+/// let dawg2: FlatDawg = ["cow"].map(from_word).into_iter().collect();
+///
+/// let any = AnyDawg::new([dawg0, dawg1, dawg2]);
+/// assert_eq!(any.len(), 4);
+/// assert!(any.has("cats"));
+/// assert!(any.has("cow"));
+/// ```
 pub struct AnyDawg<const N: usize, D>([D; N]);
 
-impl<const N: usize, D: IndexDawg<Idx = usize>> IndexDawg for AnyDawg<N, D> {
+impl<const N: usize, D> AnyDawg<N, D> {
+    /// Creates a view of the union of `dawgs`
+    pub fn new(dawgs: [D; N]) -> Self {
+        Self(dawgs)
+    }
+}
+
+impl<const N: usize, D: IndexDawg> IndexDawg for AnyDawg<N, D> {
     type Idx = [Option<D::Idx>; N];
-    const ROOT_IDX: Self::Idx = [Some(0); N];
+    const ROOT_IDX: Self::Idx = [const { Some(D::ROOT_IDX) }; N];
 
     type NodeRef<'a>
         = AnyNode<N, D::NodeRef<'a>>
     where
         Self: 'a;
     fn index(&self, idxs: [Option<D::Idx>; N]) -> Self::NodeRef<'_> {
-        AnyNode(array::from_fn(|i| idxs[i].map(|idx| self.0[i].index(idx))))
+        AnyNode(array::from_fn(|i| {
+            idxs[i].clone().map(|idx| self.0[i].index(idx))
+        }))
     }
 }
 
-impl<const N: usize, D: IndexDawg<Idx = usize>> ReadDawg for AnyDawg<N, D> {}
+impl<const N: usize, D: IndexDawg> ReadDawg for AnyDawg<N, D> {}
+
+#[cfg(test)]
+mod test {
+    use super::AnyDawg;
+    use crate::{utils::fixtures::thin_dawg, FlatDawg, ReadDawg};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..4u8, 0..6), 0..50).boxed()
+    }
+
+    proptest! {
+      #[test]
+      fn words_are_union(words0 in dawg_words(), words1 in dawg_words(), words2 in dawg_words()) {
+        let dawgs = [&words0, &words1, &words2].map(|words| thin_dawg(words));
+        let expected: BTreeSet<_> = dawgs.iter().flat_map(|dawg| dawg.words()).collect();
+
+        let dawg = AnyDawg::new(dawgs);
+        let words: Vec<_> = dawg.words().collect();
+        assert_eq!(words.len(), expected.len(), "words should be unique");
+        assert_eq!(words.into_iter().collect::<BTreeSet<_>>(), expected);
+        assert_eq!(dawg.len(), expected.len());
+        assert_eq!(dawg.is_empty(), expected.is_empty());
+      }
+
+      #[test]
+      fn has_if_any(words0 in dawg_words(), words1 in dawg_words(), word in vec(0..4u8, 0..6)) {
+        let dawgs = [&words0, &words1].map(|words| words.iter().collect::<FlatDawg>());
+        let expected = dawgs.iter().any(|dawg| dawg.has(&word));
+        assert_eq!(AnyDawg::new(dawgs).has(&word), expected);
+      }
+    }
+
+    #[test]
+    fn root_is_not_missing() {
+        // a child at index 0 isn't a missing child for the root
+        let dawg0: FlatDawg = [vec![0]].into_iter().collect();
+        let dawg1: FlatDawg = FlatDawg::default();
+        let dawg = AnyDawg::new([dawg0, dawg1]);
+        assert_eq!(dawg.words().collect::<Vec<_>>(), [vec![0]]);
+    }
+}
@@ -0,0 +1,73 @@
+use super::{IndexDawg, ReadDawg};
+use crate::node::DiffNode;
+
+/// A DAWG that has words present in the first DAWG but not the second,<br>
+/// which can be of different types.
+///
+/// ```
+/// # use odawg::{from_word, Diff, FlatDawg, ReadDawg};
+/// let dawg0: FlatDawg = ["cat", "cats", "dog"].map(from_word).into_iter().collect();
+/// let dawg1: FlatDawg = ["cat", "cow"].map(from_word).into_iter().collect();
+///
+/// let diff = Diff(&dawg0, &dawg1);
+/// assert_eq!(diff.len(), 2);
+/// assert!(diff.has("cats"));
+/// assert!(!diff.has("cat"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Diff<A, B>(pub A, pub B);
+
+impl<A: IndexDawg, B: IndexDawg> IndexDawg for Diff<A, B> {
+    type Idx = (A::Idx, Option<B::Idx>);
+    const ROOT_IDX: Self::Idx = (A::ROOT_IDX, Some(B::ROOT_IDX));
+
+    type NodeRef<'a>
+        = DiffNode<A::NodeRef<'a>, B::NodeRef<'a>>
+    where
+        Self: 'a;
+    fn index(&self, (idx0, idx1): Self::Idx) -> Self::NodeRef<'_> {
+        DiffNode(self.0.index(idx0), idx1.map(|idx| self.1.index(idx)))
+    }
+}
+
+impl<A: IndexDawg, B: IndexDawg> ReadDawg for Diff<A, B> {}
+
+#[cfg(test)]
+mod test {
+    use super::Diff;
+    use crate::{
+        pattern::Pattern,
+        utils::fixtures::{sorted_words, thin_dawg},
+        FlatDawg, ReadDawg, WriteDawg,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn matches_remove(
+        words0 in vec(vec(0..4u8, 0..6), 0..50),
+        words1 in vec(vec(0..4u8, 0..6), 0..50),
+      ) {
+        let wide: FlatDawg = words0.iter().collect();
+        let thin = thin_dawg(&words1);
+
+        let mut expected = wide.clone();
+        expected.remove(&thin);
+        assert_eq!(sorted_words(&Diff(&wide, &thin)), sorted_words(&expected));
+      }
+
+      #[test]
+      fn without_pattern(
+        words in vec(vec(0..4u8, 0..6), 0..50),
+        masks in vec(1..16u32, 0..6),
+      ) {
+        let dawg: FlatDawg = words.iter().collect();
+        let pattern = Pattern(masks);
+
+        let mut expected = dawg.clone();
+        expected.keep(|word| !pattern.has(word));
+        assert_eq!(sorted_words(&Diff(&dawg, &pattern)), sorted_words(&expected));
+      }
+    }
+}
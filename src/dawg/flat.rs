@@ -1,8 +1,8 @@
 use crate::node::NonContiguous;
 
 use super::{
-    IndexDawg, IndexMutDawg, ReadDawg, ReadNode, ThinNode, WideNode, WordIter, WriteDawg,
-    WriteNode, THIN_CHARS,
+    numbered::flat_counts, IndexDawg, IndexMutDawg, ReadDawg, ReadNode, ThinNode, WideNode,
+    WordIter, WriteDawg, WriteNode,
 };
 
 use serde::{Deserialize, Serialize};
@@ -38,36 +38,60 @@ impl<N: Display> Display for FlatDawg<N> {
 impl<N: ReadNode<Idx = usize>> FlatDawg<N> {
     /// Returns whether the DAWG has a cycle in it.<br>
     /// This can and **should** be used to catch errors.
+    ///
+    /// Each node is only searched once, so this takes time<br>
+    /// proportional to the number of nodes in the DAWG.
     pub fn is_cyclic(&self) -> bool {
-        let mut path = vec![];
-        let mut idx = Self::ROOT_IDX;
-        let mut keys: Vec<_> = self.index(Self::ROOT_IDX).keys().collect();
-
-        loop {
-            if let Some(&key) = keys.first() {
-                // we've seen this node in the path => cycle!
-                if path.iter().any(|&(i, _)| i == idx) {
-                    return true;
-                }
+        find_cycle(self, self.0.len(), [Self::ROOT_IDX]).is_some()
+    }
+}
 
-                path.push((idx, keys));
-                idx = self.index(idx).get(key);
-                keys = self.index(idx).keys().collect();
+/// Helper function to find a node that is reachable from its own children,<br>
+/// searching from each of `starts` in the `len` nodes of `dawg`.
+///
+/// This uses a depth first search, colouring nodes as:
+/// - `0`: not yet visited
+/// - `1`: currently on the search path
+/// - `2`: fully explored
+pub(crate) fn find_cycle<D: IndexDawg<Idx = usize>>(
+    dawg: &D,
+    len: usize,
+    starts: impl IntoIterator<Item = usize>,
+) -> Option<usize> {
+    let mut colour = vec![0u8; len];
+    for start in starts {
+        if colour[start] != 0 {
+            continue;
+        }
+
+        colour[start] = 1;
+        let mut stack = vec![(start, 0)];
+        while let Some((idx, c)) = stack.last_mut() {
+            let node = dawg.index(*idx);
+            let Some(k) = node.next_c(*c) else {
+                colour[*idx] = 2;
+                stack.pop();
                 continue;
+            };
+
+            *c = k + 1;
+            let c_idx = node.get(k);
+            match colour[c_idx] {
+                0 => {
+                    colour[c_idx] = 1;
+                    stack.push((c_idx, 0));
+                }
+                1 => return Some(c_idx),
+                _ => {}
             }
-
-            // last item empty => backtrack
-            let Some(item) = path.pop() else { break };
-            (idx, keys) = item;
-            keys.pop(); // we've fully explored this branch, remove it.
         }
-
-        false
     }
+
+    None
 }
 
 mod read {
-    use super::{FlatDawg, IndexDawg, ReadDawg, ReadNode, WordIter};
+    use super::{flat_counts, FlatDawg, IndexDawg, ReadDawg, ReadNode, WordIter};
 
     impl<N: ReadNode<Idx = usize>> IndexDawg for FlatDawg<N> {
         type Idx = usize;
@@ -82,7 +106,17 @@ mod read {
         }
     }
 
-    impl<N: ReadNode<Idx = usize>> ReadDawg for FlatDawg<N> {}
+    impl<N: ReadNode<Idx = usize>> ReadDawg for FlatDawg<N> {
+        /// Counts the words below each node from the bottom up,<br>
+        /// so takes time proportional to the number of nodes.
+        fn len(&self) -> usize {
+            flat_counts(self, self.0.len())[Self::ROOT_IDX]
+        }
+
+        fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+    }
 
     impl<N: ReadNode<Idx = usize>> IntoIterator for FlatDawg<N> {
         type Item = Vec<u8>;
@@ -257,22 +291,24 @@ impl<N: WriteNode<Idx = usize> + Clone + std::fmt::Debug + std::fmt::Display> Fl
     pub fn unlink(&mut self) -> bool {
         debug_assert!(!self.0.is_empty());
         debug_assert!(!self.is_cyclic(), "dawg is cyclic!\n{self}");
-        let mut stack = vec![];
-        self.on_backtrack(|_, node, _| {
-            let mut empty = !node.is_end();
-            let keys: Vec<_> = node.keys().collect();
-
-            for k in keys.into_iter().rev() {
-                let c_empty = stack.pop().expect("should have emptiness info");
-                if c_empty {
-                    *node.get_mut(k) = 0;
-                }
-                empty &= c_empty;
-            }
+        let counts = flat_counts(self, self.0.len());
 
-            stack.push(empty);
-        });
-        stack[0]
+        // nodes without any words below them are unlinked by their parents,
+        // so only the root and nodes with words below need updating
+        for (idx, node) in self.0.iter_mut().enumerate() {
+            if idx != Self::ROOT_IDX && counts[idx] == 0 {
+                continue;
+            }
+            let empty: Vec<_> = node
+                .pairs()
+                .filter(|&(_, c_idx)| counts[c_idx] == 0)
+                .map(|(c, _)| c)
+                .collect();
+            for c in empty {
+                *node.get_mut(c) = 0;
+            }
+        }
+        counts[Self::ROOT_IDX] == 0
     }
 
     /// Minimises the size of the DAWG by reusing nodes whenever possible
@@ -287,10 +323,15 @@ impl<N: WriteNode<Idx = usize> + Clone + std::fmt::Debug + std::fmt::Display> Fl
         // around the interior `for (c, mut idx0)` loop and the use of `entry`
         let mut seen: HashMap<N, usize> = HashMap::new();
         let mut stack = vec![(0, self.index(0).clone())];
+        // shared nodes only need to be minimised once
+        let mut visited = vec![false; self.0.len()];
+        visited[0] = true;
 
         while let Some(&mut (idx, ref mut node)) = stack.last_mut() {
             if let Some((_, c_idx)) = node.pop() {
-                stack.push((c_idx, self.index(c_idx).clone()));
+                if !std::mem::replace(&mut visited[c_idx], true) {
+                    stack.push((c_idx, self.index(c_idx).clone()));
+                }
                 continue;
             }
             stack.pop();
@@ -443,15 +484,15 @@ impl<N: WriteNode<Idx = usize> + Clone + std::fmt::Debug + std::fmt::Display> Fl
     }
 }
 
-impl From<FlatDawg<ThinNode>> for FlatDawg<WideNode<THIN_CHARS>> {
+impl<const N: usize> From<FlatDawg<ThinNode>> for FlatDawg<WideNode<N>> {
     fn from(value: FlatDawg<ThinNode>) -> Self {
         FlatDawg(value.0.into_iter().map(From::from).collect())
     }
 }
 
-impl TryFrom<FlatDawg<WideNode<THIN_CHARS>>> for FlatDawg<ThinNode> {
+impl<const N: usize> TryFrom<FlatDawg<WideNode<N>>> for FlatDawg<ThinNode> {
     type Error = NonContiguous;
-    fn try_from(mut value: FlatDawg<WideNode<THIN_CHARS>>) -> Result<Self, Self::Error> {
+    fn try_from(mut value: FlatDawg<WideNode<N>>) -> Result<Self, Self::Error> {
         value.trim();
         Ok(FlatDawg(
             value
@@ -467,11 +508,18 @@ impl TryFrom<FlatDawg<WideNode<THIN_CHARS>>> for FlatDawg<ThinNode> {
 mod test {
     use super::FlatDawg;
     use crate::{
-        utils::convert::{from_word, into_word},
-        ReadDawg, WideNode, WriteDawg,
+        utils::{
+            convert::{from_word, into_word},
+            fixtures::binary_words,
+        },
+        NodeIter, ReadDawg, ReadNode, ThinNode, WideNode, WriteDawg, WriteNode,
     };
     use prop::collection::vec;
     use proptest::prelude::*;
+    use std::{
+        collections::HashSet,
+        ops::{Bound, RangeBounds},
+    };
 
     fn dawg_word() -> BoxedStrategy<Vec<u8>> {
         vec(0..26u8, 0..100).boxed()
@@ -479,6 +527,15 @@ mod test {
     fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
         vec(dawg_word(), 0..100).boxed()
     }
+    fn bound() -> BoxedStrategy<Bound<Vec<u8>>> {
+        let word = || vec(0..4u8, 0..8);
+        prop_oneof![
+            word().prop_map(Bound::Included),
+            word().prop_map(Bound::Excluded),
+            Just(Bound::Unbounded),
+        ]
+        .boxed()
+    }
 
     proptest! {
       #[test]
@@ -507,6 +564,85 @@ mod test {
         assert_eq!(i_words, d_words);
       }
 
+      #[test]
+      fn words_with_prefix(
+        words in vec(vec(0..4u8, 0..8), 0..50),
+        prefix in vec(0..4u8, 0..4),
+      ) {
+        let dawg: FlatDawg = words.iter().collect();
+        let mut found: Vec<_> = dawg.words_with_prefix(&prefix).collect();
+        let mut expected: Vec<_> = dawg.words().filter(|word| word.starts_with(&prefix)).collect();
+        found.sort();
+        expected.sort();
+        assert_eq!(dawg.has_prefix(&prefix), !expected.is_empty());
+        assert_eq!(found, expected);
+      }
+
+      #[test]
+      fn words_sorted(words in vec(vec(0..4u8, 0..8), 0..50)) {
+        let dawg: FlatDawg = words.iter().collect();
+        let mut expected = words.clone();
+        expected.sort();
+        expected.dedup();
+
+        assert_eq!(dawg.words_sorted().collect::<Vec<_>>(), expected);
+        expected.reverse();
+        assert_eq!(dawg.words_rev().collect::<Vec<_>>(), expected);
+      }
+
+      #[test]
+      fn words_sorted_both_ends(words in vec(vec(0..4u8, 0..8), 0..50), ends in vec(any::<bool>(), 0..60)) {
+        let dawg: FlatDawg = words.iter().collect();
+        let expected: Vec<_> = dawg.words_sorted().collect();
+
+        let (mut front, mut back) = (vec![], vec![]);
+        let mut iter = dawg.words_sorted();
+        for from_back in ends {
+          let word = if from_back { iter.next_back() } else { iter.next() };
+          match word {
+            Some(word) if from_back => back.push(word),
+            Some(word) => front.push(word),
+            None => break,
+          }
+        }
+        front.extend(iter.by_ref());
+        assert_eq!(iter.next_back(), None);
+
+        back.reverse();
+        front.extend(back);
+        assert_eq!(front, expected);
+      }
+
+      #[test]
+      fn words_in_range(
+        words in vec(vec(0..4u8, 0..8), 0..50),
+        start in bound(),
+        end in bound(),
+      ) {
+        let dawg: FlatDawg = words.iter().collect();
+        let range = (start, end);
+        let expected: Vec<_> = dawg.words_sorted().filter(|word| range.contains(word)).collect();
+
+        assert_eq!(dawg.words_in_range(range.clone()).collect::<Vec<_>>(), expected);
+        assert_eq!(dawg.count_in_range(range.clone()), expected.len());
+        let mut found: Vec<_> = dawg.words_in_range(range.clone()).rev().collect();
+        found.reverse();
+        assert_eq!(found, expected);
+      }
+
+      #[test]
+      fn unique_nodes(words in vec(vec(0..4u8, 0..8), 0..50)) {
+        let mut dawg: FlatDawg = words.iter().collect();
+        // every node in a trie has exactly one path to it
+        assert_eq!(NodeIter::from(&dawg).unique().count(), NodeIter::from(&dawg).count());
+
+        dawg.minimise();
+        dawg.trim();
+        let reachable: HashSet<_> = dawg.reachable_nodes().collect();
+        assert_eq!(reachable.len(), dawg.reachable_nodes().count());
+        assert_eq!(reachable, (0..dawg.0.len()).collect());
+      }
+
       #[test]
       fn minimise_len(words in dawg_words()) {
         let mut dawg: FlatDawg = words.clone().into_iter().collect();
@@ -523,6 +659,42 @@ mod test {
       }
     }
 
+    #[test]
+    fn len_counts_nodes_once() {
+        let dawg = binary_words(40);
+        assert_eq!(dawg.len(), 1 << 40);
+        assert!(!dawg.is_empty());
+
+        let thin: FlatDawg<ThinNode> = FlatDawg::<ThinNode>::from_dawg(&dawg);
+        assert_eq!(thin.len(), 1 << 40);
+    }
+
+    #[test]
+    fn shared_nodes_visited_once() {
+        let mut dawg = binary_words(60);
+        assert_eq!(NodeIter::from(&dawg).unique().count(), 61);
+        assert_eq!(dawg.reachable_nodes().count(), 61);
+        assert!(!dawg.is_cyclic());
+
+        // a dead end below the root is unlinked, without visiting every path
+        let dead = dawg.insert();
+        *dawg.0[0].get_mut(2) = dead;
+        assert!(!dawg.unlink());
+        assert_eq!(dawg.0[0].get(2), 0);
+
+        dawg.minimise();
+        dawg.trim();
+        assert_eq!(dawg.0.len(), 61);
+    }
+
+    #[test]
+    fn is_cyclic() {
+        let mut dawg = binary_words(3);
+        assert!(!dawg.is_cyclic());
+        *dawg.0[3].get_mut(0) = 1;
+        assert!(dawg.is_cyclic());
+    }
+
     #[test]
     fn minimise_deletes_duplicates() {
         let mut dawg: FlatDawg = Default::default();
@@ -533,4 +705,17 @@ mod test {
         dawg.trim();
         assert_eq!(dawg.0.len(), 4);
     }
+
+    #[test]
+    fn has_invalid_words() {
+        let dawg: FlatDawg = ["cat", "cats"].map(from_word).into_iter().collect();
+        assert!(dawg.has("cat"));
+        assert!(!dawg.has("Cat"));
+        assert!(!dawg.has("cat's"));
+        assert!(!dawg.has("çat"));
+
+        let thin: FlatDawg<ThinNode> = dawg.try_into().unwrap();
+        assert!(!thin.has("cat's"));
+        assert!(!thin.has(&[2, 0, 19, 200][..]));
+    }
 }
@@ -0,0 +1,209 @@
+use super::{IndexDawg, ReadNode};
+use crate::utils::convert::TryIntoLetters;
+
+/// A node on the current path of a [`FuzzyIter`]
+struct Frame<I> {
+    idx: I,
+    /// The next letter to search from, `None` once all have been searched
+    next: Option<u8>,
+    /// The distance from the path to this node to each prefix of the target
+    row: Vec<usize>,
+}
+
+/// An iterator over the words in a DAWG within an edit distance of a target,<br>
+/// alongside their distance, in ascending lexicographic order.
+///
+/// The distances to every prefix of the target are kept for each node<br>
+/// on the current path, so that branches are skipped as soon as they're<br>
+/// guaranteed to be too far away from the target.
+pub struct FuzzyIter<D: IndexDawg> {
+    dawg: D,
+    /// The target word, where letters that couldn't be converted never match
+    target: Vec<Option<u8>>,
+    max: usize,
+    /// Whether swapping adjacent letters counts as a single edit
+    transpositions: bool,
+    stack: Vec<Frame<D::Idx>>,
+    word: Vec<u8>,
+    /// Whether the last node on the path has yet to be yielded
+    entered: bool,
+}
+
+impl<D: IndexDawg> FuzzyIter<D> {
+    /// Creates an iterator over the words within `max` edits of `target`.
+    ///
+    /// Edits are insertions, deletions and substitutions of letters,<br>
+    /// with `transpositions` also allowing adjacent letters to be swapped.
+    pub fn new(dawg: D, target: impl TryIntoLetters, max: usize, transpositions: bool) -> Self {
+        let target: Vec<_> = target.try_letters().map(Result::ok).collect();
+        let row = (0..=target.len()).collect();
+        Self {
+            dawg,
+            target,
+            max,
+            transpositions,
+            stack: vec![Frame {
+                idx: D::ROOT_IDX,
+                next: Some(0),
+                row,
+            }],
+            word: vec![],
+            entered: true,
+        }
+    }
+
+    /// Helper function to calculate the row of distances after adding `c` to the path
+    fn next_row(&self, c: u8) -> Vec<usize> {
+        let prev = &self.stack[self.stack.len() - 1].row;
+        let before = self.stack.len().checked_sub(2).map(|i| &self.stack[i].row);
+        let last = self.word.last().copied();
+
+        let mut row = Vec::with_capacity(prev.len());
+        row.push(prev[0] + 1);
+        for j in 1..prev.len() {
+            let t = self.target[j - 1];
+            let mut dist = (prev[j - 1] + usize::from(t != Some(c)))
+                .min(prev[j] + 1)
+                .min(row[j - 1] + 1);
+
+            if let Some(before) = before.filter(|_| self.transpositions && j > 1) {
+                if self.target[j - 2] == Some(c) && t == last {
+                    dist = dist.min(before[j - 2] + 1);
+                }
+            }
+            row.push(dist);
+        }
+        row
+    }
+}
+
+impl<D: IndexDawg> Iterator for FuzzyIter<D> {
+    type Item = (Vec<u8>, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let node = self.dawg.index(frame.idx.clone());
+            let dist = frame.row[self.target.len()];
+            if std::mem::take(&mut self.entered) && node.is_end() && dist <= self.max {
+                return Some((self.word.clone(), dist));
+            }
+
+            let Some(c) = frame.next.and_then(|c| node.next_c(c)) else {
+                self.stack.pop();
+                self.word.pop();
+                continue;
+            };
+            frame.next = c.checked_add(1);
+
+            let idx = node.get(c);
+            let row = self.next_row(c);
+            // distances never decrease further down the path
+            if row.iter().min().is_some_and(|&min| min > self.max) {
+                continue;
+            }
+
+            self.word.push(c);
+            self.stack.push(Frame {
+                idx,
+                next: Some(0),
+                row,
+            });
+            self.entered = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        from_word, into_word, utils::fixtures::german, Alphabet, FlatDawg, ReadDawg, WideNode,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    /// Reference implementation of the (optimal string alignment) edit distance
+    fn distance(a: &[u8], b: &[u8], transpositions: bool) -> usize {
+        let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        d[0] = (0..=b.len()).collect();
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                d[i][j] = (d[i - 1][j] + 1)
+                    .min(d[i][j - 1] + 1)
+                    .min(d[i - 1][j - 1] + cost);
+                if transpositions && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1]
+                {
+                    d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        d[a.len()][b.len()]
+    }
+
+    proptest! {
+      #[test]
+      fn matches_distance(
+        words in vec(vec(0..4u8, 0..8), 0..50),
+        target in vec(0..4u8, 0..8),
+        max in 0..4usize,
+        transpositions in any::<bool>(),
+      ) {
+        let dawg: FlatDawg = words.iter().collect();
+        let found: Vec<_> = if transpositions {
+          dawg.fuzzy_damerau(&target, max).collect()
+        } else {
+          dawg.fuzzy(&target, max).collect()
+        };
+        let expected: Vec<_> = dawg
+          .words_sorted()
+          .map(|word| {
+            let dist = distance(&word, &target, transpositions);
+            (word, dist)
+          })
+          .filter(|&(_, dist)| dist <= max)
+          .collect();
+        assert_eq!(found, expected);
+      }
+    }
+
+    #[test]
+    fn suggestions() {
+        let dawg: FlatDawg = ["cat", "cart", "act", "coat", "dog"]
+            .map(from_word)
+            .into_iter()
+            .collect();
+        let found: Vec<_> = dawg
+            .fuzzy("cta", 1)
+            .map(|(word, dist)| (into_word(word), dist))
+            .collect();
+        assert!(found.is_empty());
+
+        let found: Vec<_> = dawg
+            .fuzzy_damerau("cta", 1)
+            .map(|(word, dist)| (into_word(word), dist))
+            .collect();
+        assert_eq!(found, [("cat".to_string(), 1)]);
+
+        // characters outside the alphabet can still be substituted
+        let found: Vec<_> = dawg
+            .fuzzy("cät", 1)
+            .map(|(word, _)| into_word(word))
+            .collect();
+        assert_eq!(found, ["cat"]);
+
+        // as do symbols outside of other alphabets, without cutting the word short
+        let alphabet = german();
+        let dawg: FlatDawg<WideNode<30>> = ["garcon", "gar", "garçonne"]
+            .map(|word| alphabet.to_letters(&word.replace('ç', "c")))
+            .into_iter()
+            .collect();
+        let found: Vec<_> = dawg
+            .fuzzy(alphabet.spell("garçon"), 1)
+            .map(|(word, dist)| (alphabet.to_word(&word), dist))
+            .collect();
+        assert_eq!(found, [("garcon".to_string(), 1)]);
+    }
+}
@@ -1,6 +1,12 @@
 use super::{IndexDawg, ReadDawg, ReadNode};
 
+mod fuzzy;
+pub use fuzzy::FuzzyIter;
 mod nodes;
 pub use nodes::NodeIter;
+mod reachable;
+pub use reachable::ReachableIter;
+mod sorted;
+pub use sorted::SortedWordIter;
 mod words;
 pub use words::WordIter;
@@ -1,5 +1,10 @@
-use super::{IndexDawg, ReadDawg, ReadNode};
+use super::{IndexDawg, ReachableIter, ReadDawg, ReadNode};
+use std::hash::Hash;
 
+/// An iterator over the nodes found along every path in a DAWG.
+///
+/// Nodes shared between paths are yielded once per path,<br>
+/// use [`unique`](NodeIter::unique) to only yield them once.
 pub struct NodeIter<'a, D: IndexDawg> {
     dawg: &'a D,
     idxs: Vec<D::Idx>,
@@ -14,6 +19,18 @@ impl<'a, D: ReadDawg> From<&'a D> for NodeIter<'a, D> {
     }
 }
 
+impl<'a, D: IndexDawg> NodeIter<'a, D> {
+    /// Only yields each of the remaining nodes once, no matter<br>
+    /// how many paths lead to it, by keeping track of visited indices.
+    pub fn unique(self) -> impl Iterator<Item = D::NodeRef<'a>>
+    where
+        D::Idx: Hash + Eq,
+    {
+        let dawg = self.dawg;
+        ReachableIter::new(dawg, self.idxs).map(move |idx| dawg.index(idx))
+    }
+}
+
 impl<'a, D: IndexDawg> Iterator for NodeIter<'a, D> {
     type Item = D::NodeRef<'a>;
     fn next(&mut self) -> Option<Self::Item> {
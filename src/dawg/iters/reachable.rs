@@ -0,0 +1,54 @@
+use super::{IndexDawg, ReadDawg, ReadNode};
+use std::{collections::HashSet, hash::Hash};
+
+/// An iterator over the indices of all nodes reachable in a DAWG,<br>
+/// where each index is only yielded (and searched from) once.
+///
+/// Unlike [`NodeIter`](super::NodeIter), this takes time proportional to<br>
+/// the number of nodes in the DAWG, rather than the number of paths.
+pub struct ReachableIter<'a, D: IndexDawg> {
+    dawg: &'a D,
+    idxs: Vec<D::Idx>,
+    seen: HashSet<D::Idx>,
+}
+
+impl<'a, D: IndexDawg> ReachableIter<'a, D>
+where
+    D::Idx: Hash + Eq,
+{
+    /// Creates an iterator over the nodes reachable from any of `idxs`
+    pub fn new(dawg: &'a D, idxs: impl IntoIterator<Item = D::Idx>) -> Self {
+        let mut seen = HashSet::new();
+        let idxs = idxs
+            .into_iter()
+            .filter(|idx| seen.insert(idx.clone()))
+            .collect();
+        Self { dawg, idxs, seen }
+    }
+}
+
+impl<'a, D: ReadDawg> From<&'a D> for ReachableIter<'a, D>
+where
+    D::Idx: Hash + Eq,
+{
+    fn from(value: &'a D) -> Self {
+        Self::new(value, [D::ROOT_IDX])
+    }
+}
+
+impl<D: IndexDawg> Iterator for ReachableIter<'_, D>
+where
+    D::Idx: Hash + Eq,
+{
+    type Item = D::Idx;
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.idxs.pop()?;
+        let node = self.dawg.index(idx.clone());
+        for c_idx in node.iter() {
+            if self.seen.insert(c_idx.clone()) {
+                self.idxs.push(c_idx);
+            }
+        }
+        Some(idx)
+    }
+}
@@ -0,0 +1,179 @@
+use super::{
+    words::{descend, next_desc},
+    IndexDawg, ReadDawg, ReadNode,
+};
+use std::ops::{Bound, RangeBounds};
+
+/// An iterator over the words in a DAWG, in ascending lexicographic order.
+///
+/// Words are searched for from both ends at once, so this can also<br>
+/// be reversed to iterate over the words in descending order.
+pub struct SortedWordIter<D: IndexDawg> {
+    dawg: D,
+    /// The path to the last word found from the front,<br>
+    /// with the next letter to search from in each node
+    front: Vec<(D::Idx, Option<u8>)>,
+    front_word: Vec<u8>,
+    /// Whether the last node on the front path has yet to be yielded
+    entered: bool,
+    /// The letters left to search on the path to the last word found from the back
+    back: Vec<(D::Idx, Vec<u8>)>,
+    back_word: Vec<u8>,
+    /// The last words yielded from the front and back respectively
+    last: (Option<Vec<u8>>, Option<Vec<u8>>),
+    /// The range all yielded words must lie within
+    range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
+}
+
+impl<D: IndexDawg> SortedWordIter<D> {
+    /// Creates an iterator over the words reachable from the node at `idx`,<br>
+    /// where `word` is the path taken to reach that node from the root.
+    ///
+    /// All words yielded will start with `word`.
+    pub fn new(dawg: D, idx: D::Idx, word: Vec<u8>) -> Self {
+        SortedWordIter {
+            front: vec![(idx.clone(), Some(0))],
+            front_word: word.clone(),
+            entered: true,
+            back: vec![descend(&dawg, idx)],
+            back_word: word,
+            dawg,
+            last: (None, None),
+            range: (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
+    /// Creates an iterator over the words in `range`.
+    ///
+    /// Both ends start from their bound in the range, so<br>
+    /// only branches that can lead to words in range are searched.
+    pub fn range(dawg: D, range: impl RangeBounds<Vec<u8>>) -> Self
+    where
+        D: ReadDawg,
+    {
+        let mut iter = Self::from(dawg);
+        iter.range = (range.start_bound().cloned(), range.end_bound().cloned());
+        iter.seek_front(range.start_bound());
+        iter.seek_back(range.end_bound());
+        iter
+    }
+
+    /// Helper function to move the front path to the first word after `bound`,<br>
+    /// only following the letters of the bound's word.
+    fn seek_front(&mut self, bound: Bound<&Vec<u8>>) {
+        let (word, inclusive) = match bound {
+            Bound::Included(word) => (word, true),
+            Bound::Excluded(word) => (word, false),
+            Bound::Unbounded => return,
+        };
+
+        let (mut idx, _) = self.front.pop().expect("only called on new iterators");
+        for &c in word {
+            let node = self.dawg.index(idx.clone());
+            if !node.has(c) {
+                // the word would be a child of this node, before any larger letters
+                self.front.push((idx, Some(c)));
+                self.entered = false;
+                return;
+            }
+
+            let c_idx = node.get(c);
+            self.front.push((idx, c.checked_add(1)));
+            self.front_word.push(c);
+            idx = c_idx;
+        }
+
+        self.front.push((idx, Some(0)));
+        self.entered = inclusive;
+    }
+
+    /// Helper function to move the back path to the last word before `bound`,<br>
+    /// only following the letters of the bound's word.
+    fn seek_back(&mut self, bound: Bound<&Vec<u8>>) {
+        let (word, inclusive) = match bound {
+            Bound::Included(word) => (word, true),
+            Bound::Excluded(word) => (word, false),
+            Bound::Unbounded => return,
+        };
+
+        let (mut idx, _) = self.back.pop().expect("only called on new iterators");
+        for &c in word {
+            let node = self.dawg.index(idx.clone());
+            let keys = node.keys().take_while(|&k| k < c).collect();
+            if !node.has(c) {
+                self.back.push((idx, keys));
+                return;
+            }
+
+            let c_idx = node.get(c);
+            self.back.push((idx, keys));
+            self.back_word.push(c);
+            idx = c_idx;
+        }
+
+        if inclusive {
+            self.back.push((idx, vec![]));
+        } else {
+            self.back_word.pop();
+        }
+    }
+
+    /// Helper function to find the next word in ascending order.
+    ///
+    /// Each word is yielded as soon as its node is reached,<br>
+    /// before any of its extensions are searched.
+    fn next_asc(&mut self) -> Option<Vec<u8>> {
+        loop {
+            let (idx, next) = self.front.last_mut()?;
+            let node = self.dawg.index(idx.clone());
+            if std::mem::take(&mut self.entered) && node.is_end() {
+                return Some(self.front_word.clone());
+            }
+
+            let Some(c) = next.and_then(|c| node.next_c(c)) else {
+                self.front.pop();
+                self.front_word.pop();
+                continue;
+            };
+
+            *next = c.checked_add(1);
+            let c_idx = node.get(c);
+            self.front_word.push(c);
+            self.front.push((c_idx, Some(0)));
+            self.entered = true;
+        }
+    }
+}
+
+impl<D: ReadDawg> From<D> for SortedWordIter<D> {
+    fn from(value: D) -> Self {
+        Self::new(value, D::ROOT_IDX, vec![])
+    }
+}
+
+impl<D: IndexDawg> Iterator for SortedWordIter<D> {
+    type Item = Vec<u8>;
+    fn next(&mut self) -> Option<Vec<u8>> {
+        let word = self.next_asc()?;
+        if self.last.1.as_ref() == Some(&word) || !self.range.contains(&word) {
+            // we've met the words yielded from the back or the end of the range
+            self.front.clear();
+            return None;
+        }
+        self.last.0 = Some(word.clone());
+        Some(word)
+    }
+}
+
+impl<D: IndexDawg> DoubleEndedIterator for SortedWordIter<D> {
+    fn next_back(&mut self) -> Option<Vec<u8>> {
+        let word = next_desc(&self.dawg, &mut self.back, &mut self.back_word)?;
+        if self.last.0.as_ref() == Some(&word) || !self.range.contains(&word) {
+            // we've met the words yielded from the front or the start of the range
+            self.back.clear();
+            return None;
+        }
+        self.last.1 = Some(word.clone());
+        Some(word)
+    }
+}
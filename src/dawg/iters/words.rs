@@ -1,46 +1,73 @@
 use super::{IndexDawg, ReadDawg, ReadNode};
 
+/// An iterator over the words in a DAWG, in descending lexicographic order.
 pub struct WordIter<D: IndexDawg> {
     dawg: D,
     stack: Vec<(D::Idx, Vec<u8>)>,
     word: Vec<u8>,
 }
 
-impl<D: ReadDawg> From<D> for WordIter<D> {
-    fn from(value: D) -> Self {
-        let pair = (D::ROOT_IDX, value.index(D::ROOT_IDX).keys().collect());
+impl<D: IndexDawg> WordIter<D> {
+    /// Creates an iterator over the words reachable from the node at `idx`,<br>
+    /// where `word` is the path taken to reach that node from the root.
+    ///
+    /// All words yielded will start with `word`.
+    pub fn new(dawg: D, idx: D::Idx, word: Vec<u8>) -> Self {
         WordIter {
-            dawg: value,
-            stack: vec![pair],
-            word: vec![],
+            stack: vec![descend(&dawg, idx)],
+            dawg,
+            word,
         }
     }
 }
 
+impl<D: ReadDawg> From<D> for WordIter<D> {
+    fn from(value: D) -> Self {
+        Self::new(value, D::ROOT_IDX, vec![])
+    }
+}
+
 impl<D: IndexDawg> Iterator for WordIter<D> {
     type Item = Vec<u8>;
     fn next(&mut self) -> Option<Vec<u8>> {
-        while let Some((idx, mut keys)) = self.stack.pop() {
-            if let Some(c) = keys.pop() {
-                self.stack.push((idx.clone(), keys));
-
-                let c_idx = self.dawg.index(idx.clone()).get(c);
-                let keys = self.dawg.index(c_idx.clone()).keys().collect();
-
-                self.word.push(c);
-                self.stack.push((c_idx, keys));
-                continue;
-            }
-
-            let word = self.word.clone();
-            self.word.pop();
-            if self.dawg.index(idx.clone()).is_end() {
-                // no nodes left to explore on current node
-                // => we're currently backtracking
-                return Some(word);
-            }
+        next_desc(&self.dawg, &mut self.stack, &mut self.word)
+    }
+}
+
+/// Helper function for a stack entry to search the children of `idx`
+pub(super) fn descend<D: IndexDawg>(dawg: &D, idx: D::Idx) -> (D::Idx, Vec<u8>) {
+    let keys = dawg.index(idx.clone()).keys().collect();
+    (idx, keys)
+}
+
+/// Helper function to find the next word in descending order.
+///
+/// Each entry in `stack` holds the letters left to search in a node,<br>
+/// which are popped from the end, so the highest letters are searched first<br>
+/// and each word is only yielded once all of its extensions have been.
+pub(super) fn next_desc<D: IndexDawg>(
+    dawg: &D,
+    stack: &mut Vec<(D::Idx, Vec<u8>)>,
+    word: &mut Vec<u8>,
+) -> Option<Vec<u8>> {
+    while let Some((idx, mut keys)) = stack.pop() {
+        if let Some(c) = keys.pop() {
+            stack.push((idx.clone(), keys));
+
+            let c_idx = dawg.index(idx.clone()).get(c);
+            word.push(c);
+            stack.push(descend(dawg, c_idx));
+            continue;
         }
 
-        None
+        let found = word.clone();
+        word.pop();
+        if dawg.index(idx.clone()).is_end() {
+            // no nodes left to explore on current node
+            // => we're currently backtracking
+            return Some(found);
+        }
     }
+
+    None
 }
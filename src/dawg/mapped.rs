@@ -0,0 +1,380 @@
+use super::{
+    flat::find_cycle, numbered::flat_counts, FlatDawg, IndexDawg, ReadDawg, ReadNode, ThinNode,
+    THIN_CHARS,
+};
+use crate::node::END_MASK;
+use std::{fmt::Display, io};
+
+const MAGIC: &[u8; 4] = b"DAWG";
+const VERSION: u16 = 2;
+/// The letters supported by version 1 of the format,<br>
+/// which stored the end flag directly after them.
+const V1_CHARS: usize = 26;
+const HEADER_LEN: usize = 16;
+const NODE_LEN: usize = 8;
+
+/// Errors for when bytes can't be read as a [`MappedDawg`], or when<br>
+/// a [`PackedDawg`](super::PackedDawg) or [`VarPattern`](crate::VarPattern) can't be deserialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MapError {
+    /// The bytes are too short to contain a header, holding their length
+    Truncated(usize),
+    /// The bytes don't start with the expected magic bytes
+    Magic,
+    /// The format version isn't supported
+    Version(u16),
+    /// The alphabet has more letters than [`THIN_CHARS`]
+    Alphabet(u16),
+    /// The number of bytes doesn't match the node count in the header
+    Length {
+        /// The number of bytes the header specifies
+        expected: usize,
+        /// The number of bytes actually present
+        found: usize,
+    },
+    /// The DAWG doesn't contain a root node
+    Empty,
+    /// A node has bits set outside of its children and end flag
+    Mask(usize),
+    /// The number of bits used to index nodes doesn't match the node count
+    IndexBits(u32),
+    /// A node has children outside of the DAWG
+    Bounds(usize),
+    /// A node is reachable from its own children
+    Cyclic(usize),
+}
+
+impl Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated(n) => write!(f, "Expected a {HEADER_LEN} byte header, found {n} bytes"),
+            Self::Magic => write!(f, "Bytes are not a DAWG"),
+            Self::Version(v) => write!(f, "Unsupported format version {v}"),
+            Self::Alphabet(n) => write!(f, "Unsupported alphabet of {n} letters"),
+            Self::Length { expected, found } => {
+                write!(f, "Expected {expected} bytes, found {found}")
+            }
+            Self::Empty => write!(f, "DAWG has no root node"),
+            Self::Mask(i) => write!(f, "Node {i} has an invalid mask"),
+            Self::IndexBits(n) => write!(f, "Unexpected index width of {n} bits"),
+            Self::Bounds(i) => write!(f, "Node {i} has children out of bounds"),
+            Self::Cyclic(i) => write!(f, "Node {i} is part of a cycle"),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+/// A compact DAWG read directly from borrowed bytes,<br>
+/// i.e. from a memory mapped file, without copying any nodes.
+///
+/// ## Layout
+///
+/// All values are stored little endian, as follows:
+///
+/// | bytes     | contents                             |
+/// | --------- | ------------------------------------ |
+/// | `0..4`    | the magic bytes `b"DAWG"`            |
+/// | `4..6`    | the format version (`2`), as a `u16` |
+/// | `6..8`    | the alphabet size, as a `u16`        |
+/// | `8..16`   | the number of nodes, as a `u64`      |
+/// | `16..`    | the nodes, 8 bytes each              |
+///
+/// Each node is stored as the index of its first child (`u32`)<br>
+/// followed by its mask of children and end flag (`u32`).<br>
+/// The mask is the same as in [`ThinNode`], but the index is narrowed from a `usize`.
+///
+/// Version `1` of the format supported at most 26 letters,<br>
+/// with the end flag stored directly after them, and can still be read.
+///
+/// ```
+/// # use odawg::{from_word, FlatDawg, MappedDawg, ReadDawg, ThinNode, WideNode};
+/// let mut dawg: FlatDawg<WideNode> = ["cat", "cats"].map(from_word).into_iter().collect();
+/// dawg.trim();
+/// let dawg: FlatDawg<ThinNode> = dawg.try_into().unwrap();
+///
+/// let mut bytes = vec![];
+/// dawg.write_to(&mut bytes).unwrap();
+///
+/// let mapped = MappedDawg::new(&bytes).unwrap();
+/// assert!(mapped.has("cats"));
+/// assert_eq!(mapped.len(), 2);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MappedDawg<'a> {
+    nodes: &'a [u8],
+    end_mask: u32,
+}
+
+impl<'a> MappedDawg<'a> {
+    /// Reads a DAWG from `bytes`, validating the header, the bounds<br>
+    /// of every node and that no node can be reached from itself.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, MapError> {
+        let header = bytes
+            .get(..HEADER_LEN)
+            .ok_or(MapError::Truncated(bytes.len()))?;
+        if &header[0..4] != MAGIC {
+            return Err(MapError::Magic);
+        }
+
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        let (letters, end_mask) = match version {
+            1 => (V1_CHARS, 1 << V1_CHARS),
+            VERSION => (THIN_CHARS, END_MASK),
+            _ => return Err(MapError::Version(version)),
+        };
+
+        let alphabet = u16::from_le_bytes([header[6], header[7]]);
+        if alphabet as usize > letters {
+            return Err(MapError::Alphabet(alphabet));
+        }
+
+        let count = u64::from_le_bytes(header[8..16].try_into().expect("8 bytes"));
+        let expected = usize::try_from(count)
+            .ok()
+            .and_then(|count| count.checked_mul(NODE_LEN))
+            .and_then(|len| len.checked_add(HEADER_LEN))
+            .unwrap_or(usize::MAX);
+        if expected != bytes.len() {
+            return Err(MapError::Length {
+                expected,
+                found: bytes.len(),
+            });
+        }
+        if count == 0 {
+            return Err(MapError::Empty);
+        }
+
+        let dawg = Self {
+            nodes: &bytes[HEADER_LEN..],
+            end_mask,
+        };
+        dawg.validate((1 << alphabet) - 1)?;
+        Ok(dawg)
+    }
+
+    /// The number of nodes stored in the DAWG
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() / NODE_LEN
+    }
+
+    /// Helper function to read the index and mask of node `idx` as stored
+    fn read(&self, idx: usize) -> (u32, u32) {
+        let bytes = &self.nodes[idx * NODE_LEN..(idx + 1) * NODE_LEN];
+        (
+            u32::from_le_bytes(bytes[0..4].try_into().expect("4 bytes")),
+            u32::from_le_bytes(bytes[4..8].try_into().expect("4 bytes")),
+        )
+    }
+
+    /// Helper function to check every node's mask against the `letters`<br>
+    /// of the alphabet and then check the nodes' bounds and for cycles.
+    fn validate(&self, letters: u32) -> Result<(), MapError> {
+        let count = self.node_count();
+        for i in 0..count {
+            if self.read(i).1 & !(letters | self.end_mask) != 0 {
+                return Err(MapError::Mask(i));
+            }
+        }
+        validate_nodes(self, count)
+    }
+}
+
+/// Helper function to check that the children of the `count` nodes in `dawg`<br>
+/// are all in bounds and that no node can be reached from itself.
+pub(super) fn validate_nodes<D: IndexDawg<Idx = usize>>(
+    dawg: &D,
+    count: usize,
+) -> Result<(), MapError> {
+    for i in 0..count {
+        let node = dawg.index(i);
+        if !node.is_empty() && node.pairs().any(|(_, idx)| idx >= count) {
+            return Err(MapError::Bounds(i));
+        }
+    }
+
+    match find_cycle(dawg, count, 0..count) {
+        Some(i) => Err(MapError::Cyclic(i)),
+        None => Ok(()),
+    }
+}
+
+impl IndexDawg for MappedDawg<'_> {
+    type Idx = usize;
+    const ROOT_IDX: Self::Idx = 0;
+
+    type NodeRef<'b>
+        = ThinNode
+    where
+        Self: 'b;
+    fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        let (idx, mask) = self.read(idx);
+        let mut node = ThinNode {
+            idx: idx as usize,
+            mask: mask & !self.end_mask,
+        };
+        node.set_end(mask & self.end_mask != 0);
+        node
+    }
+}
+
+impl ReadDawg for MappedDawg<'_> {
+    fn len(&self) -> usize {
+        flat_counts(self, self.node_count())[Self::ROOT_IDX]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl FlatDawg<ThinNode> {
+    /// Writes the DAWG in the layout read by [`MappedDawg`].
+    ///
+    /// Fails if the DAWG has too many nodes to index with a `u32`.
+    pub fn write_to(&self, mut writer: impl io::Write) -> io::Result<()> {
+        if u32::try_from(self.0.len()).is_err() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "DAWG has too many nodes to be written",
+            ));
+        }
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(THIN_CHARS as u16).to_le_bytes())?;
+        writer.write_all(&(self.0.len() as u64).to_le_bytes())?;
+        for node in &self.0 {
+            writer.write_all(&(node.idx as u32).to_le_bytes())?;
+            writer.write_all(&node.mask.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl From<MappedDawg<'_>> for FlatDawg<ThinNode> {
+    fn from(value: MappedDawg<'_>) -> Self {
+        FlatDawg((0..value.node_count()).map(|i| value.index(i)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MapError, MappedDawg, HEADER_LEN};
+    use crate::{node::END_MASK, utils::fixtures::thin_dawg, FlatDawg, ReadDawg, ThinNode};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    fn to_bytes(dawg: &FlatDawg<ThinNode>) -> Vec<u8> {
+        let mut bytes = vec![];
+        dawg.write_to(&mut bytes)
+            .expect("writing to a `Vec` succeeds");
+        bytes
+    }
+
+    proptest! {
+      #[test]
+      fn round_trip(words in vec(vec(0..26u8, 0..20), 0..50)) {
+        let dawg = thin_dawg(&words);
+        let bytes = to_bytes(&dawg);
+        let mapped = MappedDawg::new(&bytes).unwrap();
+
+        let mut words0: Vec<_> = dawg.words().collect();
+        let mut words1: Vec<_> = mapped.words().collect();
+        words0.sort();
+        words1.sort();
+        assert_eq!(words0, words1);
+        assert_eq!(FlatDawg::from(mapped).0, dawg.0);
+      }
+    }
+
+    #[test]
+    fn bad_header() {
+        let bytes = to_bytes(&FlatDawg::default());
+        assert!(MappedDawg::new(&bytes).is_ok());
+
+        let mut magic = bytes.clone();
+        magic[0] = b'X';
+        assert_eq!(MappedDawg::new(&magic).unwrap_err(), MapError::Magic);
+
+        let mut version = bytes.clone();
+        version[4] = 3;
+        assert_eq!(MappedDawg::new(&version).unwrap_err(), MapError::Version(3));
+
+        let mut alphabet = bytes.clone();
+        alphabet[6] = 32;
+        assert_eq!(
+            MappedDawg::new(&alphabet).unwrap_err(),
+            MapError::Alphabet(32)
+        );
+
+        assert!(matches!(
+            MappedDawg::new(&bytes[..bytes.len() - 1]).unwrap_err(),
+            MapError::Length { .. }
+        ));
+        assert_eq!(
+            MappedDawg::new(&bytes[..5]).unwrap_err(),
+            MapError::Truncated(5)
+        );
+    }
+
+    #[test]
+    fn bad_nodes() {
+        let bytes = to_bytes(&FlatDawg(vec![
+            ThinNode { idx: 1, mask: 0b1 },
+            ThinNode { idx: 0, mask: 0 },
+        ]));
+        assert!(MappedDawg::new(&bytes).is_ok());
+
+        // letters outside of the alphabet are invalid
+        let mut mask = bytes.clone();
+        mask[6] = 1;
+        assert!(MappedDawg::new(&mask).is_ok());
+        mask[HEADER_LEN + 4] = 0b10;
+        assert_eq!(MappedDawg::new(&mask).unwrap_err(), MapError::Mask(0));
+
+        let mut bounds = bytes.clone();
+        bounds[HEADER_LEN] = 2;
+        assert_eq!(MappedDawg::new(&bounds).unwrap_err(), MapError::Bounds(0));
+
+        let mut cyclic = bytes.clone();
+        cyclic[HEADER_LEN + 8..HEADER_LEN + 16].copy_from_slice(&bytes[HEADER_LEN..HEADER_LEN + 8]);
+        assert_eq!(MappedDawg::new(&cyclic).unwrap_err(), MapError::Cyclic(1));
+    }
+
+    #[test]
+    fn version_1() {
+        let header = |alphabet: u16| {
+            let mut bytes = b"DAWG".to_vec();
+            bytes.extend(1u16.to_le_bytes());
+            bytes.extend(alphabet.to_le_bytes());
+            bytes.extend(2u64.to_le_bytes());
+            bytes
+        };
+        // "a", with the end flag stored after the 26 letters
+        let mut bytes = header(26);
+        for (idx, mask) in [(1u32, 0b1u32), (0, 1 << 26)] {
+            bytes.extend(idx.to_le_bytes());
+            bytes.extend(mask.to_le_bytes());
+        }
+
+        let mapped = MappedDawg::new(&bytes).unwrap();
+        assert_eq!(mapped.words().collect::<Vec<_>>(), [vec![0]]);
+        let dawg = FlatDawg::from(mapped);
+        assert_eq!(
+            dawg.0[1],
+            ThinNode {
+                idx: 0,
+                mask: END_MASK
+            }
+        );
+
+        // letters after the 26th are invalid
+        let mut letters = bytes.clone();
+        letters[HEADER_LEN + 4..HEADER_LEN + 8].copy_from_slice(&(1u32 << 27).to_le_bytes());
+        assert_eq!(MappedDawg::new(&letters).unwrap_err(), MapError::Mask(0));
+
+        bytes[..HEADER_LEN].copy_from_slice(&header(27));
+        assert_eq!(MappedDawg::new(&bytes).unwrap_err(), MapError::Alphabet(27));
+    }
+}
@@ -0,0 +1,275 @@
+use super::{FlatDawg, IndexDawg, ReadDawg, ReadNode, WideNode, WriteNode};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// A DAWG that is kept minimal as words are added to it.
+///
+/// This follows the incremental construction by Carrasco & Forcada:<br>
+/// alongside the nodes, we keep a register of every unique node and<br>
+/// how many parents each node has. When a word is added, any nodes<br>
+/// shared with other words are cloned before being modified and the<br>
+/// modified path is then merged back into the register.
+///
+/// Words can be added in any order, if your words are already sorted<br>
+/// [`FlatDawg::from_sorted`] will be faster and use less memory.
+#[derive(Clone, Debug)]
+pub struct MinimalDawg<N = WideNode> {
+    dawg: FlatDawg<N>,
+    register: HashMap<N, usize>,
+    parents: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl<N: Default> Default for MinimalDawg<N> {
+    fn default() -> Self {
+        Self {
+            dawg: FlatDawg::default(),
+            register: HashMap::new(),
+            parents: vec![0],
+            free: vec![],
+        }
+    }
+}
+
+impl<N: ReadNode<Idx = usize>> IndexDawg for MinimalDawg<N> {
+    type Idx = usize;
+    const ROOT_IDX: Self::Idx = 0;
+
+    type NodeRef<'a>
+        = &'a N
+    where
+        Self: 'a;
+    fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        self.dawg.index(idx)
+    }
+}
+
+impl<N: ReadNode<Idx = usize>> ReadDawg for MinimalDawg<N> {
+    fn len(&self) -> usize {
+        self.dawg.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.dawg.is_empty()
+    }
+}
+
+impl<N: WriteNode<Idx = usize> + Default + Hash + Eq + Clone> MinimalDawg<N> {
+    /// Helper function to store `node`, returning its index
+    fn insert(&mut self, node: N) -> usize {
+        for c_idx in node.iter() {
+            self.parents[c_idx] += 1;
+        }
+
+        if let Some(idx) = self.free.pop() {
+            self.dawg.0[idx] = node;
+            return idx;
+        }
+        self.dawg.0.push(node);
+        self.parents.push(0);
+        self.dawg.0.len() - 1
+    }
+
+    /// Helper function to free a node no longer linked to by any parents
+    fn delete(&mut self, idx: usize) {
+        let node = std::mem::take(&mut self.dawg.0[idx]);
+        for c_idx in node.iter() {
+            self.parents[c_idx] -= 1;
+        }
+        self.free.push(idx);
+    }
+
+    /// Helper function to point the child `c` of `idx` at `c_idx`
+    fn link(&mut self, idx: usize, c: u8, c_idx: usize) {
+        let old = std::mem::replace(self.dawg.0[idx].get_mut(c), c_idx);
+        if old != 0 {
+            self.parents[old] -= 1;
+        }
+        self.parents[c_idx] += 1;
+    }
+
+    /// Helper function to remove a node from the register before it's modified
+    fn unregister(&mut self, idx: usize) {
+        let node = &self.dawg.0[idx];
+        if self.register.get(node) == Some(&idx) {
+            self.register.remove(node);
+        }
+    }
+
+    /// Adds a single word to the DAWG, returning whether it existed.
+    ///
+    /// This is the equivalent of [`WriteDawg::add`](crate::WriteDawg::add),<br>
+    /// except the DAWG remains minimal afterwards.
+    pub fn add(&mut self, word: impl AsRef<[u8]>) -> bool {
+        let word = word.as_ref();
+
+        let mut path = vec![Self::ROOT_IDX];
+        for &c in word {
+            let node = self.index(path[path.len() - 1]);
+            if !node.has(c) {
+                break;
+            }
+            path.push(node.get(c));
+        }
+
+        if path.len() > word.len() && self.index(path[word.len()]).is_end() {
+            return true;
+        }
+
+        // everything after the first shared node is reachable by other words
+        // so we have to clone these nodes to avoid adding to other words
+        let shared = path
+            .iter()
+            .position(|&idx| self.parents[idx] > 1)
+            .unwrap_or(path.len());
+        for &idx in &path[..shared] {
+            self.unregister(idx);
+        }
+        for i in shared..path.len() {
+            let c_idx = self.insert(self.dawg.0[path[i]].clone());
+            self.link(path[i - 1], word[i - 1], c_idx);
+            path[i] = c_idx;
+        }
+
+        for &c in &word[path.len() - 1..] {
+            let c_idx = self.insert(N::default());
+            self.link(path[path.len() - 1], c, c_idx);
+            path.push(c_idx);
+        }
+        *self.dawg.0[path[word.len()]].is_end_mut() = true;
+
+        // merge the modified path back into the register
+        for i in (1..path.len()).rev() {
+            let idx = path[i];
+            match self.register.get(&self.dawg.0[idx]) {
+                Some(&r_idx) => {
+                    self.link(path[i - 1], word[i - 1], r_idx);
+                    self.delete(idx);
+                }
+                None => {
+                    self.register.insert(self.dawg.0[idx].clone(), idx);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Adds multiple words to the DAWG
+    pub fn add_all<W: AsRef<[u8]>>(&mut self, words: impl IntoIterator<Item = W>) {
+        for word in words {
+            self.add(&word);
+        }
+    }
+}
+
+impl<N> From<FlatDawg<N>> for MinimalDawg<N>
+where
+    N: WriteNode<Idx = usize> + Default + Hash + Eq + Clone + Debug + Display,
+{
+    fn from(mut dawg: FlatDawg<N>) -> Self {
+        dawg.clean();
+
+        let mut register = HashMap::new();
+        let mut parents = vec![0; dawg.0.len()];
+        for (idx, node) in dawg.0.iter().enumerate() {
+            for c_idx in node.iter() {
+                parents[c_idx] += 1;
+            }
+            if idx != Self::ROOT_IDX {
+                register.insert(node.clone(), idx);
+            }
+        }
+
+        Self {
+            dawg,
+            register,
+            parents,
+            free: vec![],
+        }
+    }
+}
+
+impl<N> From<MinimalDawg<N>> for FlatDawg<N>
+where
+    N: WriteNode<Idx = usize> + Clone + Debug + Display,
+{
+    fn from(value: MinimalDawg<N>) -> Self {
+        // freed nodes aren't linked to, so trimming removes them
+        let mut dawg = value.dawg;
+        dawg.trim();
+        dawg
+    }
+}
+
+impl<W: AsRef<[u8]>, N: WriteNode<Idx = usize> + Default + Hash + Eq + Clone> Extend<W>
+    for MinimalDawg<N>
+{
+    fn extend<T: IntoIterator<Item = W>>(&mut self, iter: T) {
+        self.add_all(iter);
+    }
+}
+
+impl<W: AsRef<[u8]>, N: WriteNode<Idx = usize> + Default + Hash + Eq + Clone> FromIterator<W>
+    for MinimalDawg<N>
+{
+    fn from_iter<T: IntoIterator<Item = W>>(iter: T) -> Self {
+        let mut dawg = Self::default();
+        dawg.extend(iter);
+        dawg
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MinimalDawg;
+    use crate::{
+        utils::{convert::from_word, fixtures::sorted_words},
+        FlatDawg, ReadDawg,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..26u8, 0..20), 0..100).boxed()
+    }
+
+    proptest! {
+      #[test]
+      fn matches_clean(words in dawg_words()) {
+        let mut dawg0: FlatDawg = words.iter().collect();
+        dawg0.clean();
+        let dawg1: MinimalDawg = words.iter().collect();
+
+        assert_eq!(sorted_words(&dawg0), sorted_words(&dawg1));
+        let dawg1: FlatDawg = dawg1.into();
+        assert_eq!(dawg0.0.len(), dawg1.0.len());
+      }
+
+      #[test]
+      fn add_to_clean(mut words0 in dawg_words(), words1 in dawg_words()) {
+        let mut dawg1: MinimalDawg = words0.iter().collect::<FlatDawg>().into();
+        dawg1.add_all(&words1);
+        words0.extend(words1);
+
+        let mut dawg0: FlatDawg = words0.iter().collect();
+        dawg0.clean();
+        assert_eq!(sorted_words(&dawg0), sorted_words(&dawg1));
+        let dawg1: FlatDawg = dawg1.into();
+        assert_eq!(dawg0.0.len(), dawg1.0.len());
+      }
+    }
+
+    #[test]
+    fn shared_suffix_is_cloned() {
+        let mut dawg: MinimalDawg = ["cat", "bat"].map(from_word).into_iter().collect();
+        assert!(!dawg.add(from_word("cats")));
+        assert!(dawg.add(from_word("cats")));
+        assert!(dawg.has("cats"));
+        assert!(!dawg.has("bats"));
+        assert_eq!(dawg.len(), 3);
+    }
+}
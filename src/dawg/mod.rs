@@ -2,11 +2,33 @@ use super::{ReadNode, ThinNode, WideNode, WriteNode, THIN_CHARS};
 
 mod all;
 pub use all::AllDawg;
+mod and;
+pub use and::And;
+mod diff;
+pub use diff::Diff;
+mod or;
+pub use or::Or;
+mod xor;
+pub use xor::Xor;
 mod flat;
 pub use flat::FlatDawg;
 mod any;
+pub use any::AnyDawg;
+mod mapped;
+pub use mapped::{MapError, MappedDawg};
+mod minimal;
+pub use minimal::MinimalDawg;
+mod numbered;
+pub use numbered::NumberedDawg;
+mod packed;
+pub use packed::PackedDawg;
+mod product;
+pub(crate) use product::{product, Finished};
+mod sorted;
+pub use sorted::Unsorted;
+mod thin;
 
 mod iters;
-pub use iters::{NodeIter, WordIter};
+pub use iters::{FuzzyIter, NodeIter, ReachableIter, SortedWordIter, WordIter};
 mod traits;
 pub use traits::{IndexDawg, IndexMutDawg, ReadDawg, WriteDawg};
@@ -0,0 +1,199 @@
+use super::{product::product, IndexDawg, ReadDawg, ReadNode};
+use std::{collections::HashMap, hash::Hash};
+
+/// Helper function to count the words below each of the `len` nodes<br>
+/// in `dawg`, where nodes are stored in an array from `0..len`.
+///
+/// Each count is memoised, so this takes time proportional to `len`,<br>
+/// unlike searching every path, which can take exponentially longer<br>
+/// for minimised DAWGs. Nodes not reachable from the root are counted as 0<br>
+/// and counts too large for a `usize` saturate at `usize::MAX`.
+pub(crate) fn flat_counts<D: IndexDawg<Idx = usize>>(dawg: &D, len: usize) -> Vec<usize> {
+    let mut counts = vec![None; len];
+    let mut stack = vec![(D::ROOT_IDX, false)];
+    while let Some((idx, expanded)) = stack.pop() {
+        if counts[idx].is_some() {
+            continue;
+        }
+
+        let node = dawg.index(idx);
+        if expanded {
+            // all children are above this node on the stack, so have been counted
+            let count = node
+                .iter()
+                .fold(usize::from(node.is_end()), |count, c_idx| {
+                    count.saturating_add(counts[c_idx].unwrap_or(0))
+                });
+            counts[idx] = Some(count);
+        } else {
+            stack.push((idx, true));
+            stack.extend(
+                node.iter()
+                    .filter(|&c_idx| counts[c_idx].is_none())
+                    .map(|c_idx| (c_idx, false)),
+            );
+        }
+    }
+    counts.into_iter().map(|count| count.unwrap_or(0)).collect()
+}
+
+/// A DAWG that stores the number of words below each of its nodes.
+///
+/// This makes [`len`](ReadDawg::len) constant time and [`rank`](ReadDawg::rank)<br>
+/// and [`select`](ReadDawg::select) take time proportional to the length<br>
+/// of the word times the size of the alphabet, giving a minimal perfect hash<br>
+/// from the words in the DAWG to `0..len()`.
+///
+/// Ranks only depend on the words stored, so they're unchanged<br>
+/// if the DAWG is minimised or trimmed before being numbered.
+///
+/// ```
+/// # use odawg::{from_word, into_word, FlatDawg, NumberedDawg, ReadDawg};
+/// let mut dawg: FlatDawg = ["cat", "cats", "fab", "fact"].map(from_word).into_iter().collect();
+/// dawg.minimise();
+/// let dawg = NumberedDawg::new(&dawg);
+///
+/// let defs = ["a small feline", "more than one cat", "fabulous", "a true thing"];
+/// let word = dawg.select(3).unwrap();
+/// assert_eq!(into_word(&word), "fact");
+/// assert_eq!(defs[dawg.rank("fact").unwrap()], "a true thing");
+/// ```
+pub struct NumberedDawg<D: IndexDawg> {
+    dawg: D,
+    counts: HashMap<D::Idx, usize>,
+}
+
+impl<D: ReadDawg> NumberedDawg<D>
+where
+    D::Idx: Hash + Eq,
+{
+    /// Counts the words below every node reachable in `dawg`.
+    ///
+    /// Each node is only counted once, so this takes time<br>
+    /// proportional to the number of nodes, not words, in `dawg`.
+    pub fn new(dawg: D) -> Self {
+        let ((end, children), memo) = product(&dawg, |(end, children)| {
+            let count = usize::from(end) + children.iter().map(|(_, n)| n).sum::<usize>();
+            (count > 0).then_some(count)
+        });
+        let root = usize::from(end) + children.iter().map(|(_, n)| n).sum::<usize>();
+
+        let mut counts: HashMap<_, _> = memo
+            .into_iter()
+            .filter_map(|(idx, count)| Some((idx, count?)))
+            .collect();
+        counts.insert(D::ROOT_IDX, root);
+        Self { dawg, counts }
+    }
+
+    /// Returns the DAWG that was numbered
+    pub fn into_inner(self) -> D {
+        self.dawg
+    }
+}
+
+impl<D: IndexDawg + Clone> Clone for NumberedDawg<D> {
+    fn clone(&self) -> Self {
+        Self {
+            dawg: self.dawg.clone(),
+            counts: self.counts.clone(),
+        }
+    }
+}
+
+impl<D: IndexDawg> IndexDawg for NumberedDawg<D> {
+    type Idx = D::Idx;
+    const ROOT_IDX: Self::Idx = D::ROOT_IDX;
+
+    type NodeRef<'a>
+        = D::NodeRef<'a>
+    where
+        Self: 'a;
+    fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        self.dawg.index(idx)
+    }
+}
+
+impl<D: ReadDawg> ReadDawg for NumberedDawg<D>
+where
+    D::Idx: Hash + Eq,
+{
+    fn node_len(&self, idx: Self::Idx) -> usize {
+        // nodes that don't lead to any words aren't stored
+        self.counts.get(&idx).copied().unwrap_or(0)
+    }
+
+    fn len(&self) -> usize {
+        self.node_len(Self::ROOT_IDX)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NumberedDawg;
+    use crate::{And, FlatDawg, ReadDawg};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..4u8, 0..8), 0..50).boxed()
+    }
+
+    proptest! {
+      #[test]
+      fn rank_matches_sorted(words in dawg_words()) {
+        let dawg: FlatDawg = words.iter().collect();
+        let numbered = NumberedDawg::new(&dawg);
+        assert_eq!(numbered.len(), dawg.len());
+
+        for (i, word) in dawg.words_sorted().enumerate() {
+          assert_eq!(numbered.rank(&word), Some(i));
+          assert_eq!(numbered.select(i), Some(word.clone()));
+          // unnumbered DAWGs give the same results, just more slowly
+          assert_eq!(dawg.rank(&word), Some(i));
+          assert_eq!(dawg.select(i), Some(word));
+        }
+        assert_eq!(numbered.select(dawg.len()), None);
+      }
+
+      #[test]
+      fn survives_minimise(words in dawg_words(), word in vec(0..4u8, 0..8)) {
+        let dawg: FlatDawg = words.iter().collect();
+        let mut minimal = dawg.clone();
+        minimal.minimise();
+        minimal.trim();
+
+        let numbered = NumberedDawg::new(&dawg);
+        let minimal = NumberedDawg::new(&minimal);
+        assert_eq!(numbered.rank(&word), minimal.rank(&word));
+        assert_eq!(numbered.rank(&word).is_some(), dawg.has(&word));
+      }
+
+      #[test]
+      fn numbers_views(words0 in dawg_words(), words1 in dawg_words()) {
+        let dawg0: FlatDawg = words0.iter().collect();
+        let dawg1: FlatDawg = words1.iter().collect();
+        let view = And(&dawg0, &dawg1);
+        let numbered = NumberedDawg::new(&view);
+
+        assert_eq!(numbered.len(), view.len());
+        for (i, word) in view.words_sorted().enumerate() {
+          assert_eq!(numbered.rank(&word), Some(i));
+        }
+      }
+    }
+
+    #[test]
+    fn invalid_words() {
+        let dawg: FlatDawg = [vec![0, 1]].into_iter().collect();
+        let numbered = NumberedDawg::new(dawg);
+        assert_eq!(numbered.rank("ab"), Some(0));
+        assert_eq!(numbered.rank("a"), None);
+        assert_eq!(numbered.rank("aB"), None);
+        assert_eq!(numbered.select(1), None);
+    }
+}
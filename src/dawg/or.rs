@@ -0,0 +1,81 @@
+use super::{IndexDawg, ReadDawg};
+use crate::node::OrNode;
+
+/// A DAWG that has words present in either contained DAWG,<br>
+/// which can be of different types.
+///
+/// More DAWGs can be combined by nesting, i.e. `Or(a, Or(b, c))`.
+///
+/// ```
+/// # use odawg::{from_word, FlatDawg, Or, ReadDawg};
+/// let dawg0: FlatDawg = ["cat", "cats"].map(from_word).into_iter().collect();
+/// let dawg1: FlatDawg = ["cat", "dog"].map(from_word).into_iter().collect();
+///
+/// let either = Or(&dawg0, &dawg1);
+/// assert_eq!(either.len(), 3);
+/// assert!(either.has("cats"));
+/// assert!(either.has("dog"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<A: IndexDawg, B: IndexDawg> IndexDawg for Or<A, B> {
+    type Idx = (Option<A::Idx>, Option<B::Idx>);
+    const ROOT_IDX: Self::Idx = (Some(A::ROOT_IDX), Some(B::ROOT_IDX));
+
+    type NodeRef<'a>
+        = OrNode<A::NodeRef<'a>, B::NodeRef<'a>>
+    where
+        Self: 'a;
+    fn index(&self, (idx0, idx1): Self::Idx) -> Self::NodeRef<'_> {
+        OrNode(
+            idx0.map(|idx| self.0.index(idx)),
+            idx1.map(|idx| self.1.index(idx)),
+        )
+    }
+}
+
+impl<A: IndexDawg, B: IndexDawg> ReadDawg for Or<A, B> {}
+
+#[cfg(test)]
+mod test {
+    use super::Or;
+    use crate::{
+        pattern::Pattern,
+        utils::fixtures::{sorted_words, thin_dawg},
+        FlatDawg, ThinNode, WriteDawg,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn matches_union(
+        words0 in vec(vec(0..4u8, 0..6), 0..50),
+        words1 in vec(vec(0..4u8, 0..6), 0..50),
+      ) {
+        let wide: FlatDawg = words0.iter().collect();
+        let thin = thin_dawg(&words1);
+
+        let mut expected = wide.clone();
+        expected.union(&thin);
+        assert_eq!(sorted_words(&Or(&wide, &thin)), sorted_words(&expected));
+        assert_eq!(sorted_words(&Or(&thin, &wide)), sorted_words(&expected));
+      }
+
+      #[test]
+      fn union_with_pattern(
+        words in vec(vec(0..4u8, 0..6), 0..50),
+        masks in vec(1..16u32, 0..4),
+      ) {
+        let dawg: FlatDawg = words.iter().collect();
+        let pattern = Pattern(masks);
+        let thin = FlatDawg::<ThinNode>::from(pattern.clone());
+
+        let mut expected = dawg.clone();
+        expected.union(&thin);
+        assert_eq!(sorted_words(&Or(&dawg, &pattern)), sorted_words(&expected));
+        assert_eq!(sorted_words(&Or(&pattern, &dawg)), sorted_words(&expected));
+      }
+    }
+}
@@ -0,0 +1,344 @@
+use super::{
+    mapped::validate_nodes, numbered::flat_counts, FlatDawg, IndexDawg, MapError, ReadDawg,
+    ReadNode, ThinNode, THIN_CHARS,
+};
+use crate::node::{CHILD_MASK, END_MASK};
+use serde::{Deserialize, Serialize};
+
+/// A compact DAWG with nodes packed into as few bits as possible.
+///
+/// Each node uses one bit for its end flag, one bit for each letter<br>
+/// up to the highest letter used in the DAWG for its children mask<br>
+/// and `ceil(log2(node_count))` bits for the index of its first child,<br>
+/// with nodes stored back to back.<br>
+/// This way smaller alphabets take up fewer bits per node.
+///
+/// Deserializing validates the nodes in the same way as [`MappedDawg::new`](crate::MappedDawg::new).
+///
+/// ```
+/// # use odawg::{from_word, FlatDawg, PackedDawg, ReadDawg, ThinNode, WideNode};
+/// let mut dawg: FlatDawg<WideNode> = ["cat", "cats"].map(from_word).into_iter().collect();
+/// dawg.trim();
+/// let dawg: FlatDawg<ThinNode> = dawg.try_into().unwrap();
+///
+/// let packed = PackedDawg::from(&dawg);
+/// // 3 bits to index 5 nodes, 20 letters from `a` to `t` and an end flag
+/// assert_eq!(packed.node_bits(), 3 + 20 + 1);
+/// assert!(packed.has("cats"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawPackedDawg")]
+pub struct PackedDawg {
+    bits: Vec<u64>,
+    idx_bits: u32,
+    letter_bits: u32,
+    len: usize,
+}
+
+/// The fields of a [`PackedDawg`], before they've been validated
+#[derive(Deserialize)]
+struct RawPackedDawg {
+    bits: Vec<u64>,
+    idx_bits: u32,
+    letter_bits: u32,
+    len: usize,
+}
+
+impl TryFrom<RawPackedDawg> for PackedDawg {
+    type Error = MapError;
+    fn try_from(value: RawPackedDawg) -> Result<Self, Self::Error> {
+        if value.len == 0 {
+            return Err(MapError::Empty);
+        }
+        if value.letter_bits as usize > THIN_CHARS {
+            let letters = u16::try_from(value.letter_bits).unwrap_or(u16::MAX);
+            return Err(MapError::Alphabet(letters));
+        }
+        if value.idx_bits != Self::idx_bits(value.len) {
+            return Err(MapError::IndexBits(value.idx_bits));
+        }
+
+        let dawg = Self {
+            bits: value.bits,
+            idx_bits: value.idx_bits,
+            letter_bits: value.letter_bits,
+            len: value.len,
+        };
+        let expected = dawg
+            .words_len()
+            .and_then(|words| words.checked_mul(8))
+            .unwrap_or(usize::MAX);
+        let found = dawg.bits.len() * 8;
+        if expected != found {
+            return Err(MapError::Length { expected, found });
+        }
+
+        validate_nodes(&dawg, dawg.len)?;
+        Ok(dawg)
+    }
+}
+
+impl PackedDawg {
+    /// The number of nodes stored in the DAWG
+    pub fn node_count(&self) -> usize {
+        self.len
+    }
+
+    /// The number of bits used to store each node
+    pub fn node_bits(&self) -> u32 {
+        self.idx_bits + self.mask_bits()
+    }
+
+    /// Helper function for the bits used by the children mask and end flag
+    fn mask_bits(&self) -> u32 {
+        self.letter_bits + 1
+    }
+
+    /// The number of bytes used to store all nodes
+    pub fn byte_len(&self) -> usize {
+        self.bits.len() * size_of::<u64>()
+    }
+
+    /// Helper function for the bits needed to index `len` nodes
+    fn idx_bits(len: usize) -> u32 {
+        usize::BITS - len.saturating_sub(1).leading_zeros()
+    }
+
+    /// Helper function for the number of `u64`s needed to store every node
+    fn words_len(&self) -> Option<usize> {
+        let total = self.len.checked_mul(self.node_bits() as usize)?;
+        Some(total.div_ceil(64))
+    }
+
+    /// Helper function for a mask of the lowest `len` bits
+    fn low_bits(len: u32) -> u64 {
+        u64::MAX.checked_shr(64 - len).unwrap_or(0)
+    }
+
+    /// Helper function to read `len` bits starting from bit `offset`
+    fn read(&self, offset: usize, len: u32) -> u64 {
+        let (i, shift) = (offset / 64, (offset % 64) as u32);
+        let mut value = self.bits.get(i).map_or(0, |bits| bits >> shift);
+        if shift + len > 64 {
+            value |= self.bits[i + 1] << (64 - shift);
+        }
+        value & Self::low_bits(len)
+    }
+
+    /// Helper function to write `len` bits of `value` starting from bit `offset`
+    fn write(&mut self, offset: usize, len: u32, value: u64) {
+        if len == 0 {
+            return;
+        }
+        let (i, shift) = (offset / 64, (offset % 64) as u32);
+        let value = value & Self::low_bits(len);
+
+        self.bits[i] |= value << shift;
+        if shift + len > 64 {
+            self.bits[i + 1] |= value >> (64 - shift);
+        }
+    }
+}
+
+impl IndexDawg for PackedDawg {
+    type Idx = usize;
+    const ROOT_IDX: Self::Idx = 0;
+
+    type NodeRef<'a>
+        = ThinNode
+    where
+        Self: 'a;
+    fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        assert!(idx < self.len, "index {idx} out of bounds");
+        let offset = idx * self.node_bits() as usize;
+        let mask = self.read(offset, self.mask_bits()) as u32;
+        ThinNode {
+            mask: (mask & !(1 << self.letter_bits)) | ((mask >> self.letter_bits) * END_MASK),
+            idx: self.read(offset + self.mask_bits() as usize, self.idx_bits) as usize,
+        }
+    }
+}
+
+impl ReadDawg for PackedDawg {
+    fn len(&self) -> usize {
+        flat_counts(self, self.len)[Self::ROOT_IDX]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Packs a compact DAWG.
+///
+/// # Panics
+/// If any node has children outside of the DAWG.
+impl From<&FlatDawg<ThinNode>> for PackedDawg {
+    fn from(value: &FlatDawg<ThinNode>) -> Self {
+        let len = value.0.len();
+        let idx_bits = Self::idx_bits(len);
+        let letters = value.0.iter().fold(0, |acc, node| acc | node.mask);
+        let letter_bits = u32::BITS - (letters & CHILD_MASK).leading_zeros();
+        let total = len * (idx_bits + letter_bits + 1) as usize;
+
+        let mut dawg = Self {
+            bits: vec![0; total.div_ceil(64)],
+            idx_bits,
+            letter_bits,
+            len,
+        };
+
+        let (node_bits, mask_bits) = (dawg.node_bits() as usize, dawg.mask_bits());
+        for (i, node) in value.0.iter().enumerate() {
+            // empty nodes can have any index, so normalise them to 0
+            let idx = if node.is_empty() { 0 } else { node.idx };
+            let children = (node.mask & CHILD_MASK).count_ones() as usize;
+            assert!(idx + children <= len, "node {i} has children out of bounds");
+
+            let end = u32::from(node.is_end()) << letter_bits;
+            let offset = i * node_bits;
+            dawg.write(offset, mask_bits, ((node.mask & CHILD_MASK) | end) as u64);
+            dawg.write(offset + mask_bits as usize, idx_bits, idx as u64);
+        }
+        dawg
+    }
+}
+
+impl From<&PackedDawg> for FlatDawg<ThinNode> {
+    fn from(value: &PackedDawg) -> Self {
+        FlatDawg((0..value.len).map(|i| value.index(i)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PackedDawg, RawPackedDawg};
+    use crate::{
+        utils::fixtures::thin_dawg, FlatDawg, MapError, ReadDawg, ReadNode, ThinNode, WideNode,
+        THIN_CHARS,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn round_trip(words in vec(vec(0..26u8, 0..20), 0..50)) {
+        let dawg = thin_dawg(&words);
+        let packed = PackedDawg::from(&dawg);
+        let unpacked = FlatDawg::from(&packed);
+
+        for (node0, node1) in dawg.0.iter().zip(&unpacked.0) {
+          assert_eq!(node0.mask, node1.mask);
+          if !node0.is_empty() {
+            assert_eq!(node0.idx, node1.idx);
+          }
+        }
+
+        let mut words0: Vec<_> = dawg.words().collect();
+        let mut words1: Vec<_> = packed.words().collect();
+        words0.sort();
+        words1.sort();
+        assert_eq!(words0, words1);
+      }
+
+      #[test]
+      fn serde_round_trip(words in vec(vec(0..26u8, 0..20), 0..50)) {
+        let packed = PackedDawg::from(&thin_dawg(&words));
+        let bytes = bincode::serialize(&packed).unwrap();
+        assert_eq!(bincode::deserialize::<PackedDawg>(&bytes).unwrap(), packed);
+      }
+    }
+
+    /// Helper function to validate `packed` as if it was deserialized
+    fn validate(packed: &PackedDawg) -> Result<PackedDawg, MapError> {
+        PackedDawg::try_from(RawPackedDawg {
+            bits: packed.bits.clone(),
+            idx_bits: packed.idx_bits,
+            letter_bits: packed.letter_bits,
+            len: packed.len,
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn pack_out_of_bounds() {
+        let dawg = FlatDawg(vec![ThinNode { idx: 1, mask: 0b11 }, ThinNode::default()]);
+        let _ = PackedDawg::from(&dawg);
+    }
+
+    #[test]
+    fn invalid_fields() {
+        let packed = PackedDawg::from(&thin_dawg(&[vec![0, 1, 2]]));
+        assert_eq!(validate(&packed), Ok(packed.clone()));
+
+        let mut empty = packed.clone();
+        empty.len = 0;
+        assert_eq!(validate(&empty), Err(MapError::Empty));
+
+        let mut letters = packed.clone();
+        letters.letter_bits = 32;
+        assert_eq!(validate(&letters), Err(MapError::Alphabet(32)));
+
+        let mut idx_bits = packed.clone();
+        idx_bits.idx_bits = 65;
+        assert_eq!(validate(&idx_bits), Err(MapError::IndexBits(65)));
+
+        let mut length = packed.clone();
+        length.bits.push(0);
+        let err = MapError::Length {
+            expected: 8,
+            found: 16,
+        };
+        assert_eq!(validate(&length), Err(err.clone()));
+
+        let bytes = bincode::serialize(&length).unwrap();
+        let msg = bincode::deserialize::<PackedDawg>(&bytes).unwrap_err();
+        assert_eq!(msg.to_string(), err.to_string());
+    }
+
+    #[test]
+    fn invalid_nodes() {
+        // a root with children `a` and `b`, where `b` is out of bounds
+        let mut bounds = PackedDawg::from(&FlatDawg(vec![ThinNode::default(); 2]));
+        bounds.letter_bits = 2;
+        bounds.bits = vec![0];
+        bounds.write(0, 3, 0b011);
+        bounds.write(3, 1, 1);
+        assert_eq!(validate(&bounds), Err(MapError::Bounds(0)));
+
+        // a root with a child `a` pointing back to the root
+        let mut cyclic = PackedDawg::from(&FlatDawg(vec![ThinNode::default()]));
+        cyclic.letter_bits = 1;
+        cyclic.write(0, 2, 0b01);
+        assert_eq!(validate(&cyclic), Err(MapError::Cyclic(0)));
+    }
+
+    #[test]
+    fn idx_bits() {
+        let packed = PackedDawg::from(&FlatDawg::<ThinNode>::default());
+        assert_eq!(packed.idx_bits, 0);
+        assert!(packed.is_empty());
+
+        let dawg = FlatDawg(vec![ThinNode::default(); 5]);
+        assert_eq!(PackedDawg::from(&dawg).idx_bits, 3);
+        let dawg = FlatDawg(vec![ThinNode::default(); 8]);
+        assert_eq!(PackedDawg::from(&dawg).idx_bits, 3);
+        let dawg = FlatDawg(vec![ThinNode::default(); 9]);
+        assert_eq!(PackedDawg::from(&dawg).idx_bits, 4);
+    }
+
+    #[test]
+    fn letter_bits() {
+        let packed = PackedDawg::from(&FlatDawg::<ThinNode>::default());
+        assert_eq!(packed.letter_bits, 0);
+
+        let dawg = thin_dawg(&[vec![0, 1, 2]]);
+        assert_eq!(PackedDawg::from(&dawg).letter_bits, 3);
+        let mut dawg: FlatDawg<WideNode<THIN_CHARS>> = [[0], [30]].iter().collect();
+        dawg.trim();
+        let packed = PackedDawg::from(&FlatDawg::try_from(dawg).unwrap());
+        assert_eq!(packed.letter_bits, 31);
+        assert!(packed.has(&[30][..]));
+        assert!(!packed.has(&[29][..]));
+    }
+}
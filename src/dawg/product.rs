@@ -0,0 +1,268 @@
+use super::{FlatDawg, IndexDawg, ReadDawg, ReadNode, ThinNode, WriteNode, THIN_CHARS};
+use std::{collections::HashMap, hash::Hash};
+
+/// The end flag and children of a node that's been fully explored
+pub(crate) type Finished<T> = (bool, Vec<(u8, T)>);
+
+/// A node on the current path of the traversal in [`product`]
+struct Frame<I, T> {
+    idx: I,
+    /// The letter used to reach this node from its parent
+    c: u8,
+    /// The next letter to search from, `None` once all have been searched
+    next: Option<u8>,
+    children: Vec<(u8, T)>,
+}
+
+/// Helper function to traverse all nodes reachable from the root of `dawg`,<br>
+/// converting each node once its children have been converted.
+///
+/// Each node is only converted once, as the result for each index is memoised,<br>
+/// so this takes time proportional to the number of distinct reachable indices.<br>
+/// `finish` should return `None` for nodes that don't lead to any words,<br>
+/// which are then left out of their parent's children.
+///
+/// The root is returned unconverted, so that it can be handled separately,<br>
+/// alongside the converted value for every other index.
+pub(crate) fn product<D: ReadDawg, T: Clone>(
+    dawg: &D,
+    mut finish: impl FnMut(Finished<T>) -> Option<T>,
+) -> (Finished<T>, HashMap<D::Idx, Option<T>>)
+where
+    D::Idx: Hash + Eq,
+{
+    let mut memo: HashMap<D::Idx, Option<T>> = HashMap::new();
+    let mut stack = vec![Frame {
+        idx: D::ROOT_IDX,
+        c: 0,
+        next: Some(0),
+        children: vec![],
+    }];
+
+    loop {
+        let frame = stack.last_mut().expect("root is only popped at the end");
+        let node = dawg.index(frame.idx.clone());
+
+        if let Some(c) = frame.next.and_then(|c| node.next_c(c)) {
+            frame.next = c.checked_add(1);
+            let idx = node.get(c);
+            match memo.get(&idx) {
+                Some(Some(value)) => frame.children.push((c, value.clone())),
+                Some(None) => {}
+                None => stack.push(Frame {
+                    idx,
+                    c,
+                    next: Some(0),
+                    children: vec![],
+                }),
+            }
+            continue;
+        }
+
+        let end = node.is_end();
+        let frame = stack.pop().expect("frame was just accessed");
+        let Some(parent) = stack.last_mut() else {
+            return ((end, frame.children), memo);
+        };
+
+        let value = finish((end, frame.children));
+        if let Some(value) = &value {
+            parent.children.push((frame.c, value.clone()));
+        }
+        memo.insert(frame.idx, value);
+    }
+}
+
+impl<N: WriteNode<Idx = usize> + Default + Hash + Eq + Clone> FlatDawg<N> {
+    /// Builds a minimal DAWG containing all the words in `dawg`.
+    ///
+    /// Rather than adding every word, this traverses `dawg` once, converting<br>
+    /// each distinct index into a node, so this takes time proportional to<br>
+    /// the number of nodes reachable in `dawg` rather than the number of words.<br>
+    /// This makes it especially useful to store lazy views, i.e. [`And`](crate::And).
+    ///
+    /// ```
+    /// # use odawg::{from_word, And, FlatDawg, ReadDawg, WideNode};
+    /// let dawg0: FlatDawg = ["cat", "cats", "fact", "facts"].map(from_word).into_iter().collect();
+    /// let dawg1: FlatDawg = ["cat", "cats", "facts", "bat"].map(from_word).into_iter().collect();
+    ///
+    /// let dawg = FlatDawg::<WideNode>::from_dawg(&And(&dawg0, &dawg1));
+    /// assert_eq!(dawg.len(), 3);
+    /// // the suffix "ts" is shared between "cats" and "facts"
+    /// assert_eq!(dawg.0.len(), 9);
+    /// ```
+    pub fn from_dawg<D: ReadDawg>(dawg: &D) -> Self
+    where
+        D::Idx: Hash + Eq,
+    {
+        // index 0 is reserved for the root, which is never registered
+        let mut nodes = vec![N::default()];
+        let mut register = HashMap::new();
+        let build = |(end, children): Finished<usize>| {
+            let mut node = N::default();
+            *node.is_end_mut() = end;
+            for (c, idx) in children {
+                *node.get_mut(c) = idx;
+            }
+            node
+        };
+
+        let (root, _) = product(dawg, |finished| {
+            if !finished.0 && finished.1.is_empty() {
+                return None;
+            }
+            let node = build(finished);
+            Some(*register.entry(node).or_insert_with_key(|node| {
+                nodes.push(node.clone());
+                nodes.len() - 1
+            }))
+        });
+
+        nodes[Self::ROOT_IDX] = build(root);
+        FlatDawg(nodes)
+    }
+}
+
+impl FlatDawg<ThinNode> {
+    /// Builds a compact DAWG containing all the words in `dawg`.
+    ///
+    /// As with the wide version of `from_dawg`, this traverses `dawg` once,<br>
+    /// sharing identical blocks of children between nodes.
+    ///
+    /// ## Panics
+    ///
+    /// If `dawg` contains letters that can't be stored in a [`ThinNode`].
+    ///
+    /// ```
+    /// # use odawg::{from_word, FlatDawg, ReadDawg, ThinNode};
+    /// let dawg: FlatDawg = ["cat", "cats", "fact", "facts"].map(from_word).into_iter().collect();
+    /// let thin = FlatDawg::<ThinNode>::from_dawg(&dawg);
+    /// assert_eq!(thin.len(), 4);
+    /// assert!(thin.has("facts"));
+    /// ```
+    pub fn from_dawg<D: ReadDawg>(dawg: &D) -> Self
+    where
+        D::Idx: Hash + Eq,
+    {
+        // index 0 is reserved for the root, which is never registered
+        let mut nodes = vec![ThinNode::default()];
+        let mut register: HashMap<Vec<ThinNode>, usize> = HashMap::new();
+        let mut build = |(end, children): Finished<ThinNode>| {
+            let mut node = ThinNode::default();
+            node.set_end(end);
+            if children.is_empty() {
+                return node;
+            }
+
+            let block: Vec<_> = children.iter().map(|&(_, child)| child).collect();
+            node.idx = *register.entry(block).or_insert_with_key(|block| {
+                nodes.extend_from_slice(block);
+                nodes.len() - block.len()
+            });
+            for (c, _) in children {
+                assert!(
+                    (c as usize) < THIN_CHARS,
+                    "letter {c} can't be stored in a thin node"
+                );
+                node.mask |= 1 << c;
+            }
+            node
+        };
+
+        let (root, _) = product(dawg, |finished| {
+            if !finished.0 && finished.1.is_empty() {
+                return None;
+            }
+            Some(build(finished))
+        });
+
+        nodes[Self::ROOT_IDX] = build(root);
+        FlatDawg(nodes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{pattern::Pattern, And, AnyDawg, FlatDawg, ReadDawg, ReadNode, ThinNode, WideNode};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..4u8, 0..8), 0..50).boxed()
+    }
+
+    fn word_set(dawg: &impl ReadDawg) -> BTreeSet<Vec<u8>> {
+        dawg.words().collect()
+    }
+
+    /// Whether every node in a thin DAWG is reachable from the root
+    fn is_trimmed(dawg: &FlatDawg<ThinNode>) -> bool {
+        let mut seen = vec![false; dawg.0.len()];
+        let mut stack = vec![0];
+        seen[0] = true;
+        while let Some(idx) = stack.pop() {
+            for c_idx in dawg.0[idx].iter() {
+                if !std::mem::replace(&mut seen[c_idx], true) {
+                    stack.push(c_idx);
+                }
+            }
+        }
+        seen.into_iter().all(|seen| seen)
+    }
+
+    proptest! {
+      #[test]
+      fn matches_clean(words in dawg_words()) {
+        let dawg: FlatDawg = words.iter().collect();
+        let mut clean = dawg.clone();
+        clean.clean();
+
+        let minimal = FlatDawg::<WideNode>::from_dawg(&dawg);
+        assert_eq!(word_set(&minimal), word_set(&dawg));
+        assert_eq!(minimal.0.len(), clean.0.len());
+      }
+
+      #[test]
+      fn thin_matches_words(words in dawg_words()) {
+        let dawg: FlatDawg = words.iter().collect();
+        let thin = FlatDawg::<ThinNode>::from_dawg(&dawg);
+        assert_eq!(word_set(&thin), word_set(&dawg));
+        assert!(is_trimmed(&thin));
+
+        // rebuilding a compact DAWG shouldn't change it
+        assert_eq!(FlatDawg::<ThinNode>::from_dawg(&thin).0, thin.0);
+      }
+
+      #[test]
+      fn stores_views(words0 in dawg_words(), words1 in dawg_words(), masks in vec(1..16u32, 0..8)) {
+        let dawg0: FlatDawg = words0.iter().collect();
+        let dawg1: FlatDawg = words1.iter().collect();
+        let pattern = Pattern(masks);
+
+        let any = AnyDawg::new([&dawg0, &dawg1]);
+        let stored = FlatDawg::<WideNode>::from_dawg(&any);
+        assert_eq!(word_set(&stored), word_set(&any));
+
+        let matching = And(&dawg0, &pattern);
+        let stored = FlatDawg::<ThinNode>::from_dawg(&matching);
+        assert_eq!(word_set(&stored), word_set(&matching));
+      }
+    }
+
+    #[test]
+    fn empty() {
+        let dawg: FlatDawg = [vec![0, 1]].into_iter().collect();
+        let pattern: Pattern = "c".parse().unwrap();
+        let empty = And(&dawg, &pattern);
+
+        assert_eq!(
+            FlatDawg::<WideNode>::from_dawg(&empty).0,
+            FlatDawg::default().0
+        );
+        assert_eq!(
+            FlatDawg::<ThinNode>::from_dawg(&empty).0,
+            FlatDawg::default().0
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use super::{FlatDawg, IndexDawg, WriteNode};
+use std::{collections::HashMap, hash::Hash};
+
+/// Error for when words passed to [`FlatDawg::from_sorted`]<br>
+/// are not in lexicographic order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unsorted {
+    /// The position of the first word that was out of order
+    pub idx: usize,
+}
+
+impl<N: WriteNode<Idx = usize> + Default + Hash + Eq + Clone> FlatDawg<N> {
+    /// Builds a minimal DAWG from words in lexicographic order.<br>
+    /// Duplicate words are allowed and only stored once.
+    ///
+    /// Unlike `collect` followed by `clean`, this never builds the full trie:<br>
+    /// nodes are registered as soon as no more words can reach them,<br>
+    /// so only the path for the latest word is ever left unminimised.
+    ///
+    /// ```
+    /// # use odawg::{from_word, FlatDawg, ReadDawg};
+    /// let words = ["cat", "cats", "fab", "face", "facet", "facets", "fact", "facts"];
+    /// let dawg: FlatDawg = FlatDawg::from_sorted(words.map(from_word)).unwrap();
+    /// assert_eq!(dawg.len(), 8);
+    /// assert!(dawg.has("facets"));
+    ///
+    /// let unsorted = ["fab", "cat"];
+    /// assert!(FlatDawg::<odawg::WideNode>::from_sorted(unsorted.map(from_word)).is_err());
+    /// ```
+    pub fn from_sorted<W: AsRef<[u8]>>(
+        words: impl IntoIterator<Item = W>,
+    ) -> Result<Self, Unsorted> {
+        // index 0 is reserved for the root, which is only finished at the end
+        let mut nodes = vec![N::default()];
+        let mut register = HashMap::new();
+        let mut path = vec![N::default()];
+        let mut last: Vec<u8> = vec![];
+
+        for (idx, word) in words.into_iter().enumerate() {
+            let word = word.as_ref();
+            if word < last.as_slice() {
+                return Err(Unsorted { idx });
+            }
+
+            let common = word
+                .iter()
+                .zip(&last)
+                .take_while(|(c0, c1)| c0 == c1)
+                .count();
+            Self::finish(&mut nodes, &mut register, &mut path, &last, common);
+
+            path.extend(word[common..].iter().map(|_| N::default()));
+            *path
+                .last_mut()
+                .expect("path contains the root")
+                .is_end_mut() = true;
+            last.clear();
+            last.extend_from_slice(word);
+        }
+
+        Self::finish(&mut nodes, &mut register, &mut path, &last, 0);
+        nodes[Self::ROOT_IDX] = path.pop().expect("path contains the root");
+        Ok(FlatDawg(nodes))
+    }
+
+    /// Helper function to register all nodes on `path` deeper than `depth`,<br>
+    /// linking each into its parent using the letters from `word`.
+    fn finish(
+        nodes: &mut Vec<N>,
+        register: &mut HashMap<N, usize>,
+        path: &mut Vec<N>,
+        word: &[u8],
+        depth: usize,
+    ) {
+        while path.len() > depth + 1 {
+            let c = word[path.len() - 2];
+            let node = path.pop().expect("path is longer than depth");
+            let idx = *register.entry(node).or_insert_with_key(|node| {
+                nodes.push(node.clone());
+                nodes.len() - 1
+            });
+
+            let parent = path.last_mut().expect("path is longer than depth");
+            *parent.get_mut(c) = idx;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Unsorted;
+    use crate::{utils::convert::from_word, FlatDawg, ReadDawg};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..26u8, 0..20), 0..100).boxed()
+    }
+
+    proptest! {
+      #[test]
+      fn matches_clean(mut words in dawg_words()) {
+        words.sort();
+
+        let mut dawg0: FlatDawg = words.iter().collect();
+        dawg0.clean();
+        let dawg1: FlatDawg = FlatDawg::from_sorted(&words).unwrap();
+
+        let mut words0: Vec<_> = dawg0.words().collect();
+        let mut words1: Vec<_> = dawg1.words().collect();
+        words0.sort();
+        words1.sort();
+        assert_eq!(words0, words1);
+        assert_eq!(dawg0.0.len(), dawg1.0.len());
+      }
+    }
+
+    #[test]
+    fn empty() {
+        let dawg: FlatDawg = FlatDawg::from_sorted::<Vec<u8>>([]).unwrap();
+        assert!(dawg.is_empty());
+        assert_eq!(dawg.0.len(), 1);
+    }
+
+    #[test]
+    fn unsorted() {
+        let words = ["cat", "fab", "cats"].map(from_word);
+        let result = FlatDawg::<crate::WideNode>::from_sorted(words);
+        assert_eq!(result.unwrap_err(), Unsorted { idx: 2 });
+    }
+}
@@ -0,0 +1,346 @@
+use super::{
+    And, Diff, FlatDawg, IndexDawg, IndexMutDawg, Or, ReadDawg, ReadNode, ThinNode, WideNode,
+    WriteDawg, THIN_CHARS,
+};
+use crate::ThinNodeMut;
+
+/// Nodes are edited through a [`ThinNodeMut`], which copies the node's<br>
+/// children into a new block if they aren't contiguous once it's dropped.
+impl IndexMutDawg for FlatDawg<ThinNode> {
+    type NodeMut<'a>
+        = ThinNodeMut<'a>
+    where
+        Self: 'a;
+    fn index_mut(&mut self, idx: Self::Idx) -> Self::NodeMut<'_> {
+        ThinNodeMut::new(&mut self.0, idx)
+    }
+}
+
+impl FlatDawg<ThinNode> {
+    /// Helper function to copy the children of `node` into a new block<br>
+    /// at the end of the DAWG, storing `child` under the letter `c`.
+    fn relocate(&mut self, node: ThinNode, c: u8, child: ThinNode) -> ThinNode {
+        let relocated = ThinNode {
+            idx: self.0.len(),
+            mask: node.mask | (1 << c),
+        };
+
+        for k in relocated.keys() {
+            let c_node = if k == c { child } else { self.0[node.get(k)] };
+            self.0.push(c_node);
+        }
+        relocated
+    }
+
+    /// Helper function to set whether `word` is an end, returning whether it was.
+    ///
+    /// As blocks of children may be shared with other words, we never<br>
+    /// modify nodes in place: the whole path to `word` is copied instead.
+    fn set_end(&mut self, word: &[u8], end: bool) -> bool {
+        let mut path = vec![self.0[Self::ROOT_IDX]];
+        for &c in word {
+            let node = path[path.len() - 1];
+            if !node.has(c) {
+                break;
+            }
+            path.push(self.0[node.get(c)]);
+        }
+
+        let existed = path.len() > word.len() && path[word.len()].is_end();
+        if existed == end {
+            return existed;
+        }
+
+        path.resize(word.len() + 1, ThinNode::default());
+        let mut node = path[word.len()];
+        node.set_end(end);
+        for (i, &c) in word.iter().enumerate().rev() {
+            node = self.relocate(path[i], c, node);
+        }
+
+        self.0[Self::ROOT_IDX] = node;
+        existed
+    }
+
+    /// Helper function to copy the words in `dawg` into a trie,<br>
+    /// as its indices can't be memoised when combining it with `self`.
+    fn trie<D: ReadDawg>(dawg: &D) -> FlatDawg<WideNode<THIN_CHARS>> {
+        let mut trie = FlatDawg::default();
+        trie.union(dawg);
+        trie
+    }
+
+    /// Removes any nodes not connected to the root node.
+    ///
+    /// Nodes are kept in the same order, so blocks of children<br>
+    /// stay contiguous and blocks shared between nodes stay shared.
+    pub fn trim(&mut self) {
+        let mut seen = vec![false; self.0.len()];
+        let mut stack = vec![Self::ROOT_IDX];
+        seen[Self::ROOT_IDX] = true;
+
+        while let Some(idx) = stack.pop() {
+            for c_idx in self.0[idx].iter() {
+                if !std::mem::replace(&mut seen[c_idx], true) {
+                    stack.push(c_idx);
+                }
+            }
+        }
+
+        let mut len = 0;
+        let idx_map: Vec<_> = seen
+            .iter()
+            .map(|&seen| {
+                len += usize::from(seen);
+                len - 1
+            })
+            .collect();
+
+        let mut seen = seen.into_iter();
+        self.0.retain(|_| seen.next().unwrap_or(false));
+        for node in self.0.iter_mut().filter(|node| !node.is_empty()) {
+            node.idx = idx_map[node.idx];
+        }
+    }
+}
+
+/// Editing for compact DAWGs.
+///
+/// Single `add`s and `sub`s copy the path to the edited word, leaving the old<br>
+/// path in place, so should be followed by a [`trim`](FlatDawg::trim).<br>
+/// The bulk methods (`add_all`, `union`, etc.) instead rebuild the DAWG once<br>
+/// from a lazy view of the result, leaving it minimal.
+impl WriteDawg for FlatDawg<ThinNode> {
+    /// Adds a single word the DAWG, returning whether it existed.
+    ///
+    /// This copies the path to `word`, so should be followed by a [`trim`](FlatDawg::trim).
+    fn add(&mut self, word: impl AsRef<[u8]>) -> bool {
+        self.set_end(word.as_ref(), true)
+    }
+
+    fn add_all<W: AsRef<[u8]>>(&mut self, words: impl IntoIterator<Item = W>) {
+        let words: FlatDawg<WideNode<THIN_CHARS>> = words.into_iter().collect();
+        *self = Self::from_dawg(&Or(&*self, &words));
+    }
+
+    fn union<D: ReadDawg>(&mut self, dawg: &D) {
+        *self = Self::from_dawg(&Or(&*self, &Self::trie(dawg)));
+    }
+
+    /// Removes a single word from the DAWG, returning whether it existed.
+    ///
+    /// This copies the path to `word`, so should be followed by a [`trim`](FlatDawg::trim).
+    fn sub(&mut self, word: impl AsRef<[u8]>) -> bool {
+        self.set_end(word.as_ref(), false)
+    }
+
+    fn sub_all<W: AsRef<[u8]>>(&mut self, words: impl IntoIterator<Item = W>) {
+        let words: FlatDawg<WideNode<THIN_CHARS>> = words.into_iter().collect();
+        *self = Self::from_dawg(&Diff(&*self, &words));
+    }
+
+    fn remove<D: ReadDawg>(&mut self, dawg: &D) {
+        *self = Self::from_dawg(&Diff(&*self, &Self::trie(dawg)));
+    }
+
+    fn keep(&mut self, f: impl Fn(&[u8]) -> bool) {
+        let words: Vec<_> = self.words().filter(|word| !f(word)).collect();
+        self.sub_all(words);
+    }
+
+    fn intersect<D: ReadDawg>(&mut self, dawg: &D) {
+        *self = Self::from_dawg(&And(&*self, &Self::trie(dawg)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        dawg::{IndexDawg, IndexMutDawg},
+        node::END_MASK,
+        utils::convert::{from_alpha, from_word},
+        utils::fixtures::{sorted_words, thin_dawg},
+        FlatDawg, ReadDawg, ReadNode, ThinNode, WideNode, WriteDawg, WriteNode,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..26u8, 0..20), 0..50).boxed()
+    }
+
+    #[test]
+    fn baseline_serde() {
+        // `["ab", "abc", "b"]`, serialized before `THIN_CHARS` was raised
+        let bytes = [
+            5, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 4, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 4, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 4,
+        ];
+        let dawg: FlatDawg<ThinNode> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(sorted_words(&dawg), ["ab", "abc", "b"].map(from_word));
+        assert_eq!(bincode::serialize(&dawg).unwrap(), bytes);
+
+        // letters past the first 26 are stored above the end flag
+        let node = |idx, mask| ThinNode { idx, mask };
+        let dawg = FlatDawg(vec![
+            node(1, 1 << 26 | 1 << 30),
+            node(3, 1 << 30),
+            node(0, END_MASK),
+            node(0, END_MASK),
+        ]);
+        let bytes = bincode::serialize(&dawg).unwrap();
+        let dawg: FlatDawg<ThinNode> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(sorted_words(&dawg), [vec![26, 30], vec![30]]);
+    }
+
+    fn mask(s: &str) -> u32 {
+        s.chars().map(|c| 1 << from_alpha(c)).sum()
+    }
+
+    proptest! {
+      #[test]
+      fn add_matches_wide(words0 in dawg_words(), words1 in dawg_words()) {
+        let mut wide: FlatDawg<WideNode> = words0.iter().collect();
+        let mut thin = thin_dawg(&words0);
+        for word in &words1 {
+          assert_eq!(wide.add(word), thin.add(word));
+        }
+        thin.trim();
+        assert_eq!(sorted_words(&wide), sorted_words(&thin));
+      }
+
+      #[test]
+      fn sub_matches_wide(words0 in dawg_words(), words1 in dawg_words()) {
+        let mut wide: FlatDawg<WideNode> = words0.iter().collect();
+        let mut thin = thin_dawg(&words0);
+        for word in words0.iter().chain(&words1).step_by(2) {
+          assert_eq!(wide.sub(word), thin.sub(word));
+        }
+        thin.trim();
+        assert_eq!(sorted_words(&wide), sorted_words(&thin));
+      }
+
+      #[test]
+      fn intersect_matches_wide(words0 in dawg_words(), words1 in dawg_words()) {
+        let mut wide: FlatDawg<WideNode> = words0.iter().collect();
+        let mut thin = thin_dawg(&words0);
+        let other: FlatDawg<WideNode> = words1.iter().collect();
+        wide.intersect(&other);
+        thin.intersect(&other);
+        assert_eq!(sorted_words(&wide), sorted_words(&thin));
+        assert_eq!(thin.0.len(), FlatDawg::<ThinNode>::from_dawg(&wide).0.len());
+      }
+
+      #[test]
+      fn union_matches_wide(words0 in dawg_words(), words1 in dawg_words()) {
+        let mut wide: FlatDawg<WideNode> = words0.iter().collect();
+        let mut thin = thin_dawg(&words0);
+        let other: FlatDawg<WideNode> = words1.iter().collect();
+        wide.union(&other);
+        thin.union(&other);
+        assert_eq!(sorted_words(&wide), sorted_words(&thin));
+        assert_eq!(thin.0.len(), FlatDawg::<ThinNode>::from_dawg(&wide).0.len());
+      }
+
+      #[test]
+      fn remove_matches_wide(words0 in dawg_words(), words1 in dawg_words()) {
+        let mut wide: FlatDawg<WideNode> = words0.iter().collect();
+        let mut thin = thin_dawg(&words0);
+        let other: FlatDawg<WideNode> = words0.iter().chain(&words1).step_by(2).collect();
+        wide.remove(&other);
+        thin.remove(&other);
+        assert_eq!(sorted_words(&wide), sorted_words(&thin));
+
+        let mut sub = thin_dawg(&words0);
+        sub.sub_all(words0.iter().chain(&words1).step_by(2));
+        assert_eq!(sub.0, thin.0);
+      }
+
+      #[test]
+      fn trim_removes_old_paths(words0 in dawg_words(), words1 in dawg_words()) {
+        let mut thin = thin_dawg(&words0);
+        thin.add_all(&words1);
+        let len = thin.0.len();
+
+        words0.iter().chain(&words1).for_each(|word| {thin.add(word);});
+        thin.trim();
+        assert_eq!(thin.0.len(), len);
+      }
+    }
+
+    #[test]
+    fn shared_blocks_are_copied() {
+        // "bat" and "cat" sharing the block for "at"
+        let mut dawg = FlatDawg(vec![
+            ThinNode {
+                idx: 1,
+                mask: mask("bc"),
+            },
+            ThinNode {
+                idx: 3,
+                mask: mask("a"),
+            },
+            ThinNode {
+                idx: 3,
+                mask: mask("a"),
+            },
+            ThinNode {
+                idx: 4,
+                mask: mask("t"),
+            },
+            ThinNode {
+                idx: 0,
+                mask: END_MASK,
+            },
+        ]);
+
+        assert!(!dawg.add(from_word("cats")));
+        assert!(dawg.has("cats"));
+        assert!(!dawg.has("bats"));
+
+        assert!(dawg.sub(from_word("bat")));
+        assert!(!dawg.has("bat"));
+        assert!(dawg.has("cat"));
+
+        dawg.trim();
+        assert_eq!(dawg.len(), 2);
+    }
+
+    #[test]
+    fn index_mut_edits_nodes() {
+        let mut dawg = thin_dawg(&[from_word("at"), from_word("it")]);
+        *dawg.index_mut(0).is_end_mut() = true;
+        assert!(dawg.has(""));
+
+        let a_idx = dawg.index(0).get(from_alpha('a'));
+        let t_idx = dawg.index(a_idx).get(from_alpha('t'));
+        dawg.index_mut(a_idx).pop();
+        assert!(!dawg.has("at"));
+        assert!(dawg.has("it"));
+
+        *dawg.index_mut(a_idx).get_mut(from_alpha('t')) = t_idx;
+        assert!(dawg.has("at"));
+    }
+
+    #[test]
+    fn index_mut_non_contiguous() {
+        let mut dawg = thin_dawg(&[from_word("ab"), from_word("ac"), from_word("dxy")]);
+        let a_idx = dawg.index(0).get(from_alpha('a'));
+        let x_idx = dawg
+            .index(dawg.index(0).get(from_alpha('d')))
+            .get(from_alpha('x'));
+        let y_idx = dawg.index(x_idx).get(from_alpha('y'));
+        *dawg.index_mut(a_idx).get_mut(from_alpha('z')) = y_idx;
+        assert_eq!(
+            sorted_words(&dawg),
+            ["ab", "ac", "az", "dxy"].map(from_word)
+        );
+
+        // the old block for `b` and `c` is left behind
+        let len = dawg.0.len();
+        dawg.trim();
+        assert_eq!(dawg.0.len(), len - 2);
+        assert!(dawg.has("az"));
+    }
+}
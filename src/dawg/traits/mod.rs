@@ -1,4 +1,6 @@
-use super::{NodeIter, ReadNode, WordIter, WriteNode};
+use super::{
+    And, FuzzyIter, NodeIter, ReachableIter, ReadNode, SortedWordIter, WordIter, WriteNode,
+};
 
 mod index;
 pub use index::{IndexDawg, IndexMutDawg};
@@ -1,5 +1,15 @@
-use super::{IndexDawg, NodeIter, ReadNode, WordIter};
-use crate::utils::convert::IntoLetters;
+use super::{
+    And, FuzzyIter, IndexDawg, NodeIter, ReachableIter, ReadNode, SortedWordIter, WordIter,
+};
+use crate::{
+    pattern::Pattern,
+    rack::{Rack, RackDawg},
+    utils::convert::TryIntoLetters,
+};
+use std::{
+    hash::Hash,
+    ops::{Bound, RangeBounds},
+};
 
 /// Methods for reading words from a DAWG
 ///
@@ -15,10 +25,41 @@ pub trait ReadDawg: Sized + IndexDawg {
         NodeIter::from(self).filter(ReadNode::is_end).count()
     }
 
-    /// Whether this DAWG contains a given word
-    fn has(&self, word: impl IntoLetters) -> bool {
+    /// How many words can be reached from the node at `idx`
+    ///
+    /// By default this searches every word below the node,<br>
+    /// see [`NumberedDawg`](crate::NumberedDawg) to store these counts.
+    fn node_len(&self, idx: Self::Idx) -> usize {
+        WordIter::new(self, idx, vec![]).count()
+    }
+
+    /// The indices of every node reachable from the root, each only once.
+    ///
+    /// This takes time proportional to the number of nodes, however<br>
+    /// many paths there are through the DAWG.
+    ///
+    /// ```
+    /// # use odawg::{from_word, FlatDawg, ReadDawg};
+    /// let mut dawg: FlatDawg = ["cat", "cats", "fact", "facts"].map(from_word).into_iter().collect();
+    /// dawg.minimise();
+    /// // "ca" and "fac" lead to the same node, so its suffixes are only visited once
+    /// assert_eq!(dawg.reachable_nodes().count(), 7);
+    /// ```
+    fn reachable_nodes(&self) -> impl Iterator<Item = Self::Idx>
+    where
+        Self::Idx: Hash + Eq,
+    {
+        ReachableIter::from(self)
+    }
+
+    /// Whether this DAWG contains a given word.<br>
+    /// Words that can't be converted into letters are never contained.
+    fn has(&self, word: impl TryIntoLetters) -> bool {
         let mut idx = Self::ROOT_IDX;
-        for c in word.letters() {
+        for c in word.try_letters() {
+            let Ok(c) = c else {
+                return false;
+            };
             let node = self.index(idx);
             if !node.has(c) {
                 return false;
@@ -28,10 +69,245 @@ pub trait ReadDawg: Sized + IndexDawg {
         self.index(idx).is_end()
     }
 
+    /// The position of `word` in [`words_sorted`](ReadDawg::words_sorted),<br>
+    /// or `None` if this DAWG doesn't contain `word`.
+    ///
+    /// This only depends on the words stored, so can be used to<br>
+    /// index a separate list of data for each word in the DAWG.
+    ///
+    /// ```
+    /// # use odawg::{from_word, FlatDawg, NumberedDawg, ReadDawg};
+    /// let dawg: FlatDawg = ["cat", "cats", "dog", "cab"].map(from_word).into_iter().collect();
+    /// let dawg = NumberedDawg::new(dawg);
+    /// assert_eq!(dawg.rank("cat"), Some(1));
+    /// assert_eq!(dawg.rank("dog"), Some(3));
+    /// assert_eq!(dawg.rank("ca"), None);
+    /// assert_eq!(dawg.select(2), Some(from_word("cats")));
+    /// ```
+    fn rank(&self, word: impl TryIntoLetters) -> Option<usize> {
+        let word: Vec<u8> = word.try_letters().collect::<Result<_, _>>().ok()?;
+        let (rank, has) = count_before(self, &word);
+        has.then_some(rank)
+    }
+
+    /// The word at position `n` in [`words_sorted`](ReadDawg::words_sorted),<br>
+    /// or `None` if this DAWG contains `n` or fewer words.
+    fn select(&self, mut n: usize) -> Option<Vec<u8>> {
+        let mut idx = Self::ROOT_IDX;
+        let mut word = vec![];
+        'search: loop {
+            let node = self.index(idx);
+            if node.is_end() {
+                if n == 0 {
+                    return Some(word);
+                }
+                n -= 1;
+            }
+
+            for (c, c_idx) in node.pairs() {
+                let len = self.node_len(c_idx.clone());
+                if n < len {
+                    word.push(c);
+                    idx = c_idx;
+                    continue 'search;
+                }
+                n -= len;
+            }
+            return None;
+        }
+    }
+
     /// All the words contained in a given DAWG
+    ///
+    /// No order is guaranteed, use [`words_sorted`](ReadDawg::words_sorted)<br>
+    /// if the words are needed in lexicographic order.
     fn words(&self) -> impl Iterator<Item = Vec<u8>> {
         WordIter::from(self)
     }
+
+    /// All the words contained in this DAWG, in ascending lexicographic order,<br>
+    /// where each word comes before any words that extend it.
+    ///
+    /// This can be reversed, and taken from both ends at once.
+    ///
+    /// ```
+    /// # use odawg::{from_word, into_word, FlatDawg, ReadDawg};
+    /// let dawg: FlatDawg = ["cats", "cat", "dog", "cab"].map(from_word).into_iter().collect();
+    /// let words: Vec<_> = dawg.words_sorted().map(into_word).collect();
+    /// assert_eq!(words, ["cab", "cat", "cats", "dog"]);
+    ///
+    /// let mut words = dawg.words_sorted().map(into_word);
+    /// assert_eq!(words.next_back().unwrap(), "dog");
+    /// assert_eq!(words.next().unwrap(), "cab");
+    /// assert_eq!(words.collect::<Vec<_>>(), ["cat", "cats"]);
+    /// ```
+    fn words_sorted(&self) -> impl DoubleEndedIterator<Item = Vec<u8>> {
+        SortedWordIter::from(self)
+    }
+
+    /// All the words contained in this DAWG, in descending lexicographic order,<br>
+    /// i.e. [`words_sorted`](ReadDawg::words_sorted) reversed.
+    fn words_rev(&self) -> impl DoubleEndedIterator<Item = Vec<u8>> {
+        self.words_sorted().rev()
+    }
+
+    /// Whether any word in this DAWG starts with `prefix`
+    ///
+    /// As DAWGs may contain nodes that don't lead to any words,<br>
+    /// this searches for the first word below the prefix.
+    fn has_prefix(&self, prefix: impl TryIntoLetters) -> bool {
+        self.words_with_prefix(prefix).next().is_some()
+    }
+
+    /// All the words in this DAWG that lie within `range`, in ascending order.
+    ///
+    /// Only the branches between the ends of the range are searched,<br>
+    /// so this can be combined with [`count_in_range`](ReadDawg::count_in_range)<br>
+    /// and [`select`](ReadDawg::select) to page through the words.
+    ///
+    /// ```
+    /// # use odawg::{from_word, into_word, FlatDawg, NumberedDawg, ReadDawg};
+    /// let words = ["cat", "cats", "fab", "face", "facet", "facets", "fact", "facts"];
+    /// let dawg: FlatDawg = words.map(from_word).into_iter().collect();
+    ///
+    /// let found: Vec<_> = dawg.words_in_range(from_word("fab")..from_word("fact")).map(into_word).collect();
+    /// assert_eq!(found, ["fab", "face", "facet", "facets"]);
+    /// assert_eq!(dawg.count_in_range(from_word("fab")..from_word("fact")), 4);
+    ///
+    /// // the 2nd page of 2 words after "cats"
+    /// let dawg = NumberedDawg::new(&dawg);
+    /// let start = dawg.count_in_range(..=from_word("cats")) + 2;
+    /// let page: Vec<_> = dawg.words_in_range(dawg.select(start).unwrap()..).take(2).map(into_word).collect();
+    /// assert_eq!(page, ["facet", "facets"]);
+    /// ```
+    fn words_in_range(
+        &self,
+        range: impl RangeBounds<Vec<u8>>,
+    ) -> impl DoubleEndedIterator<Item = Vec<u8>> {
+        SortedWordIter::range(self, range)
+    }
+
+    /// The number of words in this DAWG that lie within `range`
+    ///
+    /// This only follows the letters of the ends of the range,<br>
+    /// so is fast for DAWGs with fast [`node_len`](ReadDawg::node_len)s.
+    fn count_in_range(&self, range: impl RangeBounds<Vec<u8>>) -> usize {
+        let start = match range.start_bound() {
+            Bound::Included(word) => count_before(self, word).0,
+            Bound::Excluded(word) => {
+                let (count, has) = count_before(self, word);
+                count + usize::from(has)
+            }
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(word) => {
+                let (count, has) = count_before(self, word);
+                count + usize::from(has)
+            }
+            Bound::Excluded(word) => count_before(self, word).0,
+            Bound::Unbounded => self.len(),
+        };
+        end.saturating_sub(start)
+    }
+
+    /// All the words in this DAWG that start with `prefix`
+    ///
+    /// ```
+    /// # use odawg::{from_word, into_word, FlatDawg, ReadDawg};
+    /// let dawg: FlatDawg = ["cat", "cats", "dog", "cab"].map(from_word).into_iter().collect();
+    /// let mut words: Vec<_> = dawg.words_with_prefix("ca").map(into_word).collect();
+    /// words.sort();
+    /// assert_eq!(words, ["cab", "cat", "cats"]);
+    /// assert!(dawg.has_prefix("do"));
+    /// assert!(!dawg.has_prefix("cow"));
+    /// ```
+    fn words_with_prefix(&self, prefix: impl TryIntoLetters) -> impl Iterator<Item = Vec<u8>> {
+        let mut idx = Self::ROOT_IDX;
+        let mut word = vec![];
+        for c in prefix.try_letters() {
+            let node = self.index(idx.clone());
+            let Some(c) = c.ok().filter(|&c| node.has(c)) else {
+                return None.into_iter().flatten();
+            };
+            idx = node.get(c);
+            word.push(c);
+        }
+        Some(WordIter::new(self, idx, word)).into_iter().flatten()
+    }
+
+    /// All the words in this DAWG that match `pattern`.
+    ///
+    /// The DAWG and the pattern are traversed together, so only<br>
+    /// the paths allowed by both are ever visited.
+    ///
+    /// ```
+    /// # use odawg::{from_word, into_word, FlatDawg, ReadDawg};
+    /// let dawg: FlatDawg = ["cat", "cot", "cut", "cats"].map(from_word).into_iter().collect();
+    /// let pattern = "c[ao]t".parse().unwrap();
+    /// let mut words: Vec<_> = dawg.matching(&pattern).map(into_word).collect();
+    /// words.sort();
+    /// assert_eq!(words, ["cat", "cot"]);
+    /// ```
+    fn matching<'a>(&'a self, pattern: &'a Pattern) -> impl Iterator<Item = Vec<u8>> + 'a {
+        WordIter::from(And(self, pattern))
+    }
+
+    /// All the words in this DAWG within `max_distance` edits of `word`,<br>
+    /// alongside their distance, in ascending lexicographic order.
+    ///
+    /// Edits are insertions, deletions or substitutions of single letters,<br>
+    /// i.e. the Levenshtein distance. Characters in `word` that can't be<br>
+    /// converted into letters never match, but can still be edited.
+    ///
+    /// ```
+    /// # use odawg::{from_word, into_word, FlatDawg, ReadDawg};
+    /// let dawg: FlatDawg = ["cat", "cart", "act", "coat", "dog"].map(from_word).into_iter().collect();
+    /// let found: Vec<_> = dawg.fuzzy("cot", 1).map(|(word, dist)| (into_word(word), dist)).collect();
+    /// assert_eq!(found, [("cat".to_string(), 1), ("coat".to_string(), 1)]);
+    /// ```
+    fn fuzzy(
+        &self,
+        word: impl TryIntoLetters,
+        max_distance: usize,
+    ) -> impl Iterator<Item = (Vec<u8>, usize)> {
+        FuzzyIter::new(self, word, max_distance, false)
+    }
+
+    /// As with [`fuzzy`](ReadDawg::fuzzy), but also allowing swaps of<br>
+    /// adjacent letters as a single edit, i.e. the Damerau-Levenshtein<br>
+    /// (optimal string alignment) distance.
+    ///
+    /// ```
+    /// # use odawg::{from_word, into_word, FlatDawg, ReadDawg};
+    /// let dawg: FlatDawg = ["cat", "act", "dog"].map(from_word).into_iter().collect();
+    /// let found: Vec<_> = dawg.fuzzy_damerau("cta", 1).map(|(word, _)| into_word(word)).collect();
+    /// assert_eq!(found, ["cat"]);
+    /// ```
+    fn fuzzy_damerau(
+        &self,
+        word: impl TryIntoLetters,
+        max_distance: usize,
+    ) -> impl Iterator<Item = (Vec<u8>, usize)> {
+        FuzzyIter::new(self, word, max_distance, true)
+    }
+
+    /// All the words in this DAWG that can be spelled using<br>
+    /// every tile in `rack`, see [`Rack`] for an example.
+    ///
+    /// The DAWG is searched while taking tiles from the rack,<br>
+    /// so only prefixes that can be spelled are ever visited.
+    fn anagrams<'a>(&'a self, rack: &'a Rack) -> impl Iterator<Item = Vec<u8>> + 'a {
+        WordIter::from(And(self, RackDawg::anagrams(rack)))
+    }
+
+    /// All the words in this DAWG that can be spelled using<br>
+    /// any of the tiles in `rack`, see [`Rack`] for an example.
+    ///
+    /// Use [`Rack::blanks_in`] to find which letters blanks were used for.
+    fn sub_anagrams<'a>(&'a self, rack: &'a Rack) -> impl Iterator<Item = Vec<u8>> + 'a {
+        WordIter::from(And(self, RackDawg::sub_anagrams(rack)))
+    }
 }
 
 impl<D: ReadDawg> ReadDawg for &D {
@@ -41,10 +317,89 @@ impl<D: ReadDawg> ReadDawg for &D {
     fn len(&self) -> usize {
         <D as ReadDawg>::len(self)
     }
-    fn has(&self, word: impl IntoLetters) -> bool {
+    fn node_len(&self, idx: Self::Idx) -> usize {
+        <D as ReadDawg>::node_len(self, idx)
+    }
+    fn reachable_nodes(&self) -> impl Iterator<Item = Self::Idx>
+    where
+        Self::Idx: Hash + Eq,
+    {
+        <D as ReadDawg>::reachable_nodes(self)
+    }
+    fn has(&self, word: impl TryIntoLetters) -> bool {
         <D as ReadDawg>::has(self, word)
     }
+    fn rank(&self, word: impl TryIntoLetters) -> Option<usize> {
+        <D as ReadDawg>::rank(self, word)
+    }
+    fn select(&self, n: usize) -> Option<Vec<u8>> {
+        <D as ReadDawg>::select(self, n)
+    }
     fn words(&self) -> impl Iterator<Item = Vec<u8>> {
         <D as ReadDawg>::words(self)
     }
+    fn words_sorted(&self) -> impl DoubleEndedIterator<Item = Vec<u8>> {
+        <D as ReadDawg>::words_sorted(self)
+    }
+    fn words_rev(&self) -> impl DoubleEndedIterator<Item = Vec<u8>> {
+        <D as ReadDawg>::words_rev(self)
+    }
+    fn has_prefix(&self, prefix: impl TryIntoLetters) -> bool {
+        <D as ReadDawg>::has_prefix(self, prefix)
+    }
+    fn words_in_range(
+        &self,
+        range: impl RangeBounds<Vec<u8>>,
+    ) -> impl DoubleEndedIterator<Item = Vec<u8>> {
+        <D as ReadDawg>::words_in_range(self, range)
+    }
+    fn count_in_range(&self, range: impl RangeBounds<Vec<u8>>) -> usize {
+        <D as ReadDawg>::count_in_range(self, range)
+    }
+    fn words_with_prefix(&self, prefix: impl TryIntoLetters) -> impl Iterator<Item = Vec<u8>> {
+        <D as ReadDawg>::words_with_prefix(self, prefix)
+    }
+    fn matching<'a>(&'a self, pattern: &'a Pattern) -> impl Iterator<Item = Vec<u8>> + 'a {
+        <D as ReadDawg>::matching(self, pattern)
+    }
+    fn fuzzy(
+        &self,
+        word: impl TryIntoLetters,
+        max_distance: usize,
+    ) -> impl Iterator<Item = (Vec<u8>, usize)> {
+        <D as ReadDawg>::fuzzy(self, word, max_distance)
+    }
+    fn fuzzy_damerau(
+        &self,
+        word: impl TryIntoLetters,
+        max_distance: usize,
+    ) -> impl Iterator<Item = (Vec<u8>, usize)> {
+        <D as ReadDawg>::fuzzy_damerau(self, word, max_distance)
+    }
+    fn anagrams<'a>(&'a self, rack: &'a Rack) -> impl Iterator<Item = Vec<u8>> + 'a {
+        <D as ReadDawg>::anagrams(self, rack)
+    }
+    fn sub_anagrams<'a>(&'a self, rack: &'a Rack) -> impl Iterator<Item = Vec<u8>> + 'a {
+        <D as ReadDawg>::sub_anagrams(self, rack)
+    }
+}
+
+/// Helper function for the number of words in `dawg` that come before `word`,<br>
+/// and whether `dawg` contains `word`.
+fn count_before<D: ReadDawg>(dawg: &D, word: &[u8]) -> (usize, bool) {
+    let mut idx = D::ROOT_IDX;
+    let mut count = 0;
+    for &c in word {
+        let node = dawg.index(idx);
+        count += usize::from(node.is_end());
+        for (_, c_idx) in node.pairs().take_while(|&(k, _)| k < c) {
+            count += dawg.node_len(c_idx);
+        }
+
+        if !node.has(c) {
+            return (count, false);
+        }
+        idx = node.get(c);
+    }
+    (count, dawg.index(idx).is_end())
 }
@@ -0,0 +1,89 @@
+use super::{IndexDawg, ReadDawg};
+use crate::node::{OrNode, XorNode};
+
+/// A DAWG that has words present in exactly one of the contained DAWGs,<br>
+/// which can be of different types.
+///
+/// ```
+/// # use odawg::{from_word, FlatDawg, ReadDawg, Xor};
+/// let dawg0: FlatDawg = ["cat", "cats", "dog"].map(from_word).into_iter().collect();
+/// let dawg1: FlatDawg = ["cat", "cow"].map(from_word).into_iter().collect();
+///
+/// let xor = Xor(&dawg0, &dawg1);
+/// assert_eq!(xor.len(), 3);
+/// assert!(xor.has("cats"));
+/// assert!(xor.has("cow"));
+/// assert!(!xor.has("cat"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Xor<A, B>(pub A, pub B);
+
+impl<A: IndexDawg, B: IndexDawg> IndexDawg for Xor<A, B> {
+    type Idx = (Option<A::Idx>, Option<B::Idx>);
+    const ROOT_IDX: Self::Idx = (Some(A::ROOT_IDX), Some(B::ROOT_IDX));
+
+    type NodeRef<'a>
+        = XorNode<A::NodeRef<'a>, B::NodeRef<'a>>
+    where
+        Self: 'a;
+    fn index(&self, (idx0, idx1): Self::Idx) -> Self::NodeRef<'_> {
+        XorNode(OrNode(
+            idx0.map(|idx| self.0.index(idx)),
+            idx1.map(|idx| self.1.index(idx)),
+        ))
+    }
+}
+
+impl<A: IndexDawg, B: IndexDawg> ReadDawg for Xor<A, B> {}
+
+#[cfg(test)]
+mod test {
+    use super::Xor;
+    use crate::{utils::fixtures::thin_dawg, And, AnyDawg, Diff, FlatDawg, Or, ReadDawg};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn dawg_words() -> BoxedStrategy<Vec<Vec<u8>>> {
+        vec(vec(0..4u8, 0..6), 0..50).boxed()
+    }
+
+    fn word_set(dawg: &impl ReadDawg) -> BTreeSet<Vec<u8>> {
+        dawg.words().collect()
+    }
+
+    proptest! {
+      #[test]
+      fn matches_symmetric_difference(words0 in dawg_words(), words1 in dawg_words()) {
+        let wide: FlatDawg = words0.iter().collect();
+        let thin = thin_dawg(&words1);
+
+        let expected = &word_set(&wide) ^ &word_set(&thin);
+        assert_eq!(word_set(&Xor(&wide, &thin)), expected);
+        assert_eq!(word_set(&Xor(&thin, &wide)), expected);
+        assert_eq!(Xor(&wide, &thin).len(), expected.len());
+      }
+
+      #[test]
+      fn composes_with_combinators(
+        words0 in dawg_words(),
+        words1 in dawg_words(),
+        words2 in dawg_words(),
+      ) {
+        let dawg0: FlatDawg = words0.iter().collect();
+        let dawg1 = thin_dawg(&words1);
+        let dawg2: FlatDawg = words2.iter().collect();
+        let (set0, set1, set2) = (word_set(&dawg0), word_set(&dawg1), word_set(&dawg2));
+
+        // xor is the union without the intersection
+        let xor = Diff(Or(&dawg0, &dawg1), And(&dawg0, &dawg1));
+        assert_eq!(word_set(&xor), &set0 ^ &set1);
+
+        let diff = Diff(Xor(&dawg0, &dawg1), &dawg2);
+        assert_eq!(word_set(&diff), &(&set0 ^ &set1) - &set2);
+
+        let any = Xor(AnyDawg::new([&dawg0, &dawg2]), &dawg1);
+        assert_eq!(word_set(&any), &(&set0 | &set2) ^ &set1);
+      }
+    }
+}
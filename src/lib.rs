@@ -81,10 +81,21 @@
     clippy::style
 )]
 mod pattern;
+pub use pattern::{
+    ErrorKind, LetterClasses, ParseError, Pattern, PatternDisplay, ShapePattern, VarPattern,
+};
+mod rack;
+pub use rack::Rack;
 mod utils;
-pub use utils::{from_word, into_word, serde_array, EndSort};
+pub use utils::{
+    from_word, into_word, serde_array, try_from_word, Alphabet, EndSort, FromWords, Latin,
+    LetterError, Spelling, Tiles, TryIntoLetters, LATIN_CHARS,
+};
 mod dawg;
-pub use dawg::{AllDawg, FlatDawg, ReadDawg, WriteDawg};
+pub use dawg::{
+    AllDawg, And, AnyDawg, Diff, FlatDawg, MapError, MappedDawg, MinimalDawg, NodeIter,
+    NumberedDawg, Or, PackedDawg, ReachableIter, ReadDawg, Unsorted, WriteDawg, Xor,
+};
 mod node;
 use node::{ReadNode, WriteNode};
-pub use node::{ThinNode, WideNode, THIN_CHARS};
+pub use node::{ThinNode, ThinNodeMut, WideNode, THIN_CHARS};
@@ -22,6 +22,21 @@ impl<const NUM: usize, N: ReadNode<Idx = usize>> ReadNode for AllNode<NUM, N> {
     fn get(&self, c: u8) -> Self::Idx {
         array::from_fn(|i| self.0[i].get(c))
     }
+
+    fn next_c(&self, mut c: u8) -> Option<u8> {
+        // leapfrog between nodes until they all agree on a child
+        loop {
+            let mut agreed = true;
+            for node in &self.0 {
+                let next = node.next_c(c)?;
+                agreed &= next == c;
+                c = next;
+            }
+            if agreed {
+                return Some(c);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,77 @@
+use super::ReadNode;
+
+/// A node that only has children present in both of its nodes,<br>
+/// where the nodes can be of different types.
+///
+/// Children are found by iterating over the first node and<br>
+/// checking the second, so the first node should be the sparser one.
+#[derive(Clone, Copy)]
+pub struct AndNode<A, B>(pub(crate) A, pub(crate) B);
+
+impl<A: ReadNode, B: ReadNode> ReadNode for AndNode<A, B> {
+    type Idx = (A::Idx, B::Idx);
+
+    fn is_end(&self) -> bool {
+        self.0.is_end() && self.1.is_end()
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.0.has(c) && self.1.has(c)
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        (self.0.get(c), self.1.get(c))
+    }
+
+    fn next_c(&self, mut c: u8) -> Option<u8> {
+        loop {
+            c = self.0.next_c(c)?;
+            if self.1.has(c) {
+                return Some(c);
+            }
+            c = c.checked_add(1)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AndNode;
+    use crate::node::{
+        thin::{test::thin_node, END_MASK},
+        ReadNode, ThinNode, WideNode,
+    };
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn end_only_if_both() {
+        let end = ThinNode {
+            idx: 0,
+            mask: END_MASK,
+        };
+        let not_end = ThinNode::default();
+        assert!(AndNode(end, end).is_end());
+        assert!(!AndNode(end, not_end).is_end());
+        assert!(!AndNode(not_end, end).is_end());
+        assert!(!AndNode(not_end, not_end).is_end());
+    }
+
+    proptest! {
+      #[test]
+      fn keys_intersect(node0 in thin_node(), node1 in thin_node()) {
+        let wide = WideNode::<31>::from(node1);
+        let node = AndNode(node0, wide);
+
+        let keys0: HashSet<_> = node0.keys().collect();
+        let keys1: HashSet<_> = node1.keys().collect();
+        let keys: HashSet<_> = node.keys().collect();
+        assert_eq!(keys, &keys0 & &keys1);
+        assert_eq!(node.is_end(), node0.is_end() && node1.is_end());
+
+        for c in keys {
+          assert_eq!(node.get(c), (node0.get(c), node1.get(c)));
+        }
+      }
+    }
+}
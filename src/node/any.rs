@@ -1,16 +1,20 @@
 use super::ReadNode;
 use std::array;
 
+/// A node that has children present in any of its nodes.
+///
+/// A missing node (`None`) is treated as an empty node, as<br>
+/// happens when one DAWG has a branch that the others don't.
 #[derive(Clone, Copy)]
 pub struct AnyNode<const NUM: usize, N>(pub(crate) [Option<N>; NUM]);
 
-impl<const NUM: usize, N: ReadNode<Idx = usize>> ReadNode for AnyNode<NUM, N> {
-    type Idx = [Option<usize>; NUM];
+impl<const NUM: usize, N: ReadNode> ReadNode for AnyNode<NUM, N> {
+    type Idx = [Option<N::Idx>; NUM];
 
     fn is_empty(&self) -> bool {
         self.0
             .iter()
-            .all(|node| node.as_ref().is_some_and(ReadNode::is_empty))
+            .all(|node| node.as_ref().is_none_or(ReadNode::is_empty))
     }
 
     fn is_end(&self) -> bool {
@@ -28,10 +32,16 @@ impl<const NUM: usize, N: ReadNode<Idx = usize>> ReadNode for AnyNode<NUM, N> {
     fn get(&self, c: u8) -> Self::Idx {
         array::from_fn(|i| {
             let node = self.0[i].as_ref()?;
-            let i = node.get(c);
-            (i != 0).then_some(i)
+            node.has(c).then(|| node.get(c))
         })
     }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        self.0
+            .iter()
+            .filter_map(|node| node.as_ref()?.next_c(c))
+            .min()
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +89,23 @@ mod test {
         assert!(!anode.is_empty());
     }
 
+    #[test]
+    fn missing_is_empty() {
+        let anode = AnyNode::<2, ThinNode>([None, None]);
+        assert!(anode.is_empty());
+        let anode = AnyNode([None, Some(thin_mask(0b0))]);
+        assert!(anode.is_empty());
+        let anode = AnyNode([None, Some(thin_mask(0b1))]);
+        assert!(!anode.is_empty());
+    }
+
+    #[test]
+    fn child_at_zero_is_present() {
+        let node = ThinNode { idx: 0, mask: 0b1 };
+        let anode = AnyNode([Some(node), None]);
+        assert_eq!(anode.get(0), [Some(0), None]);
+    }
+
     #[test]
     fn neither_union_not_end() {
         let node0 = thin_mask(0b0);
@@ -0,0 +1,70 @@
+use super::ReadNode;
+
+/// A node that has all the children of its first node, where<br>
+/// words are only ended if they aren't ended in its second node.
+///
+/// A missing second node (`None`) is treated as an empty node,<br>
+/// as happens when the first DAWG has a branch that the second doesn't.
+#[derive(Clone, Copy)]
+pub struct DiffNode<A, B>(pub(crate) A, pub(crate) Option<B>);
+
+impl<A: ReadNode, B: ReadNode> ReadNode for DiffNode<A, B> {
+    type Idx = (A::Idx, Option<B::Idx>);
+
+    fn is_end(&self) -> bool {
+        self.0.is_end() && !self.1.as_ref().is_some_and(ReadNode::is_end)
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.0.has(c)
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        let idx = self
+            .1
+            .as_ref()
+            .filter(|node| node.has(c))
+            .map(|node| node.get(c));
+        (self.0.get(c), idx)
+    }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        self.0.next_c(c)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DiffNode;
+    use crate::node::{
+        thin::{test::thin_node, END_MASK},
+        ReadNode, ThinNode,
+    };
+    use proptest::prelude::*;
+
+    #[test]
+    fn end_only_if_not_removed() {
+        let end = ThinNode {
+            idx: 0,
+            mask: END_MASK,
+        };
+        let not_end = ThinNode::default();
+        assert!(DiffNode(end, Some(not_end)).is_end());
+        assert!(DiffNode::<_, ThinNode>(end, None).is_end());
+        assert!(!DiffNode(end, Some(end)).is_end());
+        assert!(!DiffNode(not_end, Some(not_end)).is_end());
+    }
+
+    proptest! {
+      #[test]
+      fn keys_from_first(node0 in thin_node(), node1 in thin_node()) {
+        let node = DiffNode(node0, Some(node1));
+        assert_eq!(node.keys().collect::<Vec<_>>(), node0.keys().collect::<Vec<_>>());
+
+        for c in node.keys() {
+          let idx1 = node1.has(c).then(|| node1.get(c));
+          assert_eq!(node.get(c), (node0.get(c), idx1));
+        }
+      }
+    }
+}
@@ -1,7 +1,16 @@
 mod all;
 pub use all::AllNode;
+mod and;
+pub use and::AndNode;
+mod diff;
+pub use diff::DiffNode;
+mod or;
+pub use or::OrNode;
+mod xor;
+pub use xor::XorNode;
 mod thin;
-pub use thin::{NonContiguous, ThinNode, THIN_CHARS};
+pub use thin::{NonContiguous, ThinNode, ThinNodeMut, THIN_CHARS};
+pub(crate) use thin::{CHILD_MASK, END_MASK};
 mod wide;
 pub use wide::WideNode;
 mod any;
@@ -58,6 +67,9 @@ pub trait ReadNode: Sized {
 impl<N: ReadNode> ReadNode for &N {
     type Idx = <N as ReadNode>::Idx;
 
+    fn len(&self) -> usize {
+        N::len(self)
+    }
     fn is_empty(&self) -> bool {
         N::is_empty(self)
     }
@@ -70,10 +82,16 @@ impl<N: ReadNode> ReadNode for &N {
     fn get(&self, c: u8) -> Self::Idx {
         N::get(self, c)
     }
+    fn next_c(&self, c: u8) -> Option<u8> {
+        N::next_c(self, c)
+    }
 }
 impl<N: ReadNode> ReadNode for &mut N {
     type Idx = <N as ReadNode>::Idx;
 
+    fn len(&self) -> usize {
+        N::len(self)
+    }
     fn is_empty(&self) -> bool {
         N::is_empty(self)
     }
@@ -86,6 +104,9 @@ impl<N: ReadNode> ReadNode for &mut N {
     fn get(&self, c: u8) -> Self::Idx {
         N::get(self, c)
     }
+    fn next_c(&self, c: u8) -> Option<u8> {
+        N::next_c(self, c)
+    }
 }
 
 /// A node that can set have its contents modified.
@@ -0,0 +1,94 @@
+use super::ReadNode;
+
+/// A node that has children present in either of its nodes,<br>
+/// where the nodes can be of different types.
+///
+/// A missing node (`None`) is treated as an empty node, as<br>
+/// happens when one DAWG has a branch that the other doesn't.
+#[derive(Clone, Copy)]
+pub struct OrNode<A, B>(pub(crate) Option<A>, pub(crate) Option<B>);
+
+impl<A: ReadNode, B: ReadNode> ReadNode for OrNode<A, B> {
+    type Idx = (Option<A::Idx>, Option<B::Idx>);
+
+    fn is_end(&self) -> bool {
+        self.0.as_ref().is_some_and(ReadNode::is_end)
+            || self.1.as_ref().is_some_and(ReadNode::is_end)
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.0.as_ref().is_some_and(|node| node.has(c))
+            || self.1.as_ref().is_some_and(|node| node.has(c))
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        (
+            self.0
+                .as_ref()
+                .filter(|node| node.has(c))
+                .map(|node| node.get(c)),
+            self.1
+                .as_ref()
+                .filter(|node| node.has(c))
+                .map(|node| node.get(c)),
+        )
+    }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        let c0 = self.0.as_ref().and_then(|node| node.next_c(c));
+        let c1 = self.1.as_ref().and_then(|node| node.next_c(c));
+        c0.into_iter().chain(c1).min()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OrNode;
+    use crate::node::{
+        thin::{test::thin_node, END_MASK},
+        ReadNode, ThinNode, WideNode,
+    };
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn end_if_either() {
+        let end = ThinNode {
+            idx: 0,
+            mask: END_MASK,
+        };
+        let not_end = ThinNode::default();
+        assert!(OrNode(Some(end), Some(not_end)).is_end());
+        assert!(OrNode(Some(not_end), Some(end)).is_end());
+        assert!(OrNode::<_, ThinNode>(Some(end), None).is_end());
+        assert!(!OrNode(Some(not_end), Some(not_end)).is_end());
+        assert!(!OrNode::<ThinNode, ThinNode>(None, None).is_end());
+    }
+
+    proptest! {
+      #[test]
+      fn keys_union(node0 in thin_node(), node1 in thin_node()) {
+        let wide = WideNode::<31>::from(node1);
+        let node = OrNode(Some(node0), Some(wide));
+
+        let keys0: HashSet<_> = node0.keys().collect();
+        let keys1: HashSet<_> = node1.keys().collect();
+        let keys: HashSet<_> = node.keys().collect();
+        assert_eq!(keys, &keys0 | &keys1);
+        assert_eq!(node.is_end(), node0.is_end() || node1.is_end());
+
+        for c in keys {
+          let idx0 = node0.has(c).then(|| node0.get(c));
+          let idx1 = node1.has(c).then(|| node1.get(c));
+          assert_eq!(node.get(c), (idx0, idx1));
+        }
+      }
+
+      #[test]
+      fn missing_is_empty(node0 in thin_node()) {
+        let node = OrNode::<_, ThinNode>(Some(node0), None);
+        assert_eq!(node.keys().collect::<Vec<_>>(), node0.keys().collect::<Vec<_>>());
+        assert_eq!(node.is_end(), node0.is_end());
+      }
+    }
+}
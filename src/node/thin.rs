@@ -1,21 +1,69 @@
-use super::{ReadNode, WideNode};
+use super::{ReadNode, WideNode, WriteNode};
 use serde::{Deserialize, Serialize};
 
 /// The number of character that thin Dawg nodes support.<br>
 /// Can be up to 31 characters at most, due to implementation constraints.
-pub const THIN_CHARS: usize = 26;
+pub const THIN_CHARS: usize = 31;
 pub(crate) const CHILD_MASK: u32 = (1 << THIN_CHARS) - 1;
 pub(crate) const END_MASK: u32 = 1 << THIN_CHARS;
 
+/// The number of characters thin nodes supported before the end flag moved,<br>
+/// which is where the end flag is still stored when serialized.
+const SERDE_CHARS: usize = 26;
+const SERDE_CHILD_MASK: u32 = (1 << SERDE_CHARS) - 1;
+const SERDE_END_MASK: u32 = 1 << SERDE_CHARS;
+
 /// Memory efficient Dawg nodes.<br>
 /// Relies on the assumption that all children are contiguous.
+///
+/// These serialize with the end flag after the first 26 children,<br>
+/// so DAWGs serialized with 26 letters still deserialize the same.
 // @note leaf nodes serialize to be empty
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "SerdeThinNode", into = "SerdeThinNode")]
 pub struct ThinNode {
     pub(crate) idx: usize,
     pub(crate) mask: u32,
 }
 
+/// A [`ThinNode`] as it's serialized, with children after<br>
+/// the first 26 stored above the end flag.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename = "ThinNode")]
+struct SerdeThinNode {
+    idx: usize,
+    mask: u32,
+}
+
+impl From<ThinNode> for SerdeThinNode {
+    fn from(node: ThinNode) -> Self {
+        let end = if node.is_end() { SERDE_END_MASK } else { 0 };
+        let mask = (node.mask & SERDE_CHILD_MASK)
+            | end
+            | ((node.mask & CHILD_MASK) >> SERDE_CHARS) << (SERDE_CHARS + 1);
+        Self {
+            idx: node.idx,
+            mask,
+        }
+    }
+}
+
+impl From<SerdeThinNode> for ThinNode {
+    fn from(node: SerdeThinNode) -> Self {
+        let end = if node.mask & SERDE_END_MASK > 0 {
+            END_MASK
+        } else {
+            0
+        };
+        let mask =
+            (node.mask & SERDE_CHILD_MASK) | end | (node.mask >> (SERDE_CHARS + 1)) << SERDE_CHARS;
+        Self {
+            idx: node.idx,
+            mask,
+        }
+    }
+}
+
 impl ReadNode for ThinNode {
     type Idx = usize;
 
@@ -32,7 +80,7 @@ impl ReadNode for ThinNode {
     }
 
     fn has(&self, i: u8) -> bool {
-        ((self.mask >> i) & 1) > 0
+        (i as usize) < THIN_CHARS && ((self.mask >> i) & 1) > 0
     }
 
     fn get(&self, i: u8) -> Self::Idx {
@@ -46,17 +94,102 @@ impl ReadNode for ThinNode {
     }
 
     fn next_c(&self, c: u8) -> Option<u8> {
-        let masked = self.mask & !((1 << c) - 1);
+        if c as usize >= THIN_CHARS {
+            return None;
+        }
+        let masked = self.mask & CHILD_MASK & !((1 << c) - 1);
         (masked > 0).then(|| masked.trailing_zeros() as u8)
     }
 }
 
+impl ThinNode {
+    /// Sets whether the current node is the end of a word
+    pub(crate) fn set_end(&mut self, end: bool) {
+        if end {
+            self.mask |= END_MASK;
+        } else {
+            self.mask &= !END_MASK;
+        }
+    }
+}
+
+/// A mutable reference to a node in a compact DAWG, edited as a [`WideNode`].
+///
+/// Changes are written back to the node when this is dropped. If the node's<br>
+/// children are no longer contiguous, they're copied into a new block at<br>
+/// the end of the DAWG, leaving the old block to be removed by a `trim`.
+pub struct ThinNodeMut<'a> {
+    nodes: &'a mut Vec<ThinNode>,
+    idx: usize,
+    wide: WideNode<THIN_CHARS>,
+}
+
+impl<'a> ThinNodeMut<'a> {
+    /// Edits the node at `idx` in `nodes`
+    pub(crate) fn new(nodes: &'a mut Vec<ThinNode>, idx: usize) -> Self {
+        let wide = WideNode::from(nodes[idx]);
+        Self { nodes, idx, wide }
+    }
+}
+
+impl Drop for ThinNodeMut<'_> {
+    fn drop(&mut self) {
+        let node = ThinNode::try_from(self.wide).unwrap_or_else(|NonContiguous| {
+            // copies of the children have the same children themselves
+            let mut node = ThinNode {
+                idx: self.nodes.len(),
+                mask: if self.wide.end { END_MASK } else { 0 },
+            };
+            for (c, c_idx) in self.wide.pairs() {
+                node.mask |= 1 << c;
+                let child = self.nodes[c_idx];
+                self.nodes.push(child);
+            }
+            node
+        });
+        self.nodes[self.idx] = node;
+    }
+}
+
+impl ReadNode for ThinNodeMut<'_> {
+    type Idx = usize;
+
+    fn len(&self) -> usize {
+        self.wide.len()
+    }
+    fn is_empty(&self) -> bool {
+        self.wide.is_empty()
+    }
+    fn is_end(&self) -> bool {
+        self.wide.is_end()
+    }
+    fn has(&self, c: u8) -> bool {
+        self.wide.has(c)
+    }
+    fn get(&self, c: u8) -> Self::Idx {
+        self.wide.get(c)
+    }
+    fn next_c(&self, c: u8) -> Option<u8> {
+        self.wide.next_c(c)
+    }
+}
+
+impl WriteNode for ThinNodeMut<'_> {
+    fn is_end_mut(&mut self) -> &mut bool {
+        self.wide.is_end_mut()
+    }
+    fn get_mut(&mut self, c: u8) -> &mut Self::Idx {
+        self.wide.get_mut(c)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NonContiguous;
 
-impl TryFrom<WideNode<26>> for ThinNode {
+impl<const N: usize> TryFrom<WideNode<N>> for ThinNode {
     type Error = NonContiguous;
-    fn try_from(value: WideNode<26>) -> Result<Self, Self::Error> {
+    fn try_from(value: WideNode<N>) -> Result<Self, Self::Error> {
+        const { assert!(N <= THIN_CHARS, "too many children for a thin node") };
         let mut mask = if value.end { END_MASK } else { 0 };
 
         let mut iter = value.pairs();
@@ -123,6 +256,13 @@ pub(crate) mod test {
       fn len_eq_bits((len, node) in len_node_pair()) {
         assert_eq!(node.len(), len);
       }
+
+      #[test]
+      fn serde_round_trip(mut node in thin_node(), end: bool) {
+        node.set_end(end);
+        let bytes = bincode::serialize(&node).unwrap();
+        assert_eq!(bincode::deserialize::<ThinNode>(&bytes).unwrap(), node);
+      }
     }
 
     #[test]
@@ -172,8 +312,17 @@ pub(crate) mod test {
       /// the `next_c` should return the same as the default implementation
       #[test]
       fn next_c_matches((node, c) in (thin_node(), 0u8..THIN_CHARS as u8)) {
-        let next = (c..26).find(|&c| node.has(c));
+        let next = (c..THIN_CHARS as u8).find(|&c| node.has(c));
         assert_eq!(node.next_c(c), next)
       }
+
+      /// the end flag shouldn't be treated as a child
+      #[test]
+      fn end_not_key(mut node in thin_node()) {
+        let keys0: Vec<_> = node.keys().collect();
+        node.set_end(true);
+        let keys1: Vec<_> = node.keys().collect();
+        assert_eq!(keys0, keys1);
+      }
     }
 }
@@ -1,12 +1,15 @@
-use super::{ReadNode, ThinNode, WriteNode, THIN_CHARS};
-use crate::utils::{convert::into_alpha, serde_array};
+use super::{ReadNode, ThinNode, WriteNode};
+use crate::utils::{serde_array, Alphabet, Latin, LATIN_CHARS};
 use serde::{Deserialize, Serialize};
 use std::{array, fmt::Display};
 
 /// A full width node, capable of representing children<br>
 /// that are potentially non-contiguous.
+///
+/// Supports alphabets of up to `N` letters, with the default<br>
+/// being large enough for the [`Latin`] alphabet.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
-pub struct WideNode<const N: usize = THIN_CHARS> {
+pub struct WideNode<const N: usize = LATIN_CHARS> {
     pub(crate) end: bool,
     #[serde(with = "serde_array")]
     pub(crate) children: [usize; N],
@@ -26,7 +29,10 @@ impl<const N: usize> Display for WideNode<N> {
         let end_str = if self.is_end() { "end]" } else { "   ]" };
         write!(f, "{end_str}")?;
         for c in self.keys() {
-            write!(f, " {}: {}", into_alpha(c), self.get(c))?;
+            match Latin.symbol(c) {
+                Some(symbol) => write!(f, " {symbol}: {}", self.get(c))?,
+                None => write!(f, " #{c}: {}", self.get(c))?,
+            }
         }
         Ok(())
     }
@@ -48,11 +54,17 @@ impl<const N: usize> ReadNode for WideNode<N> {
     }
 
     fn has(&self, c: u8) -> bool {
-        self.children[c as usize] > 0
+        self.get(c) > 0
     }
 
     fn get(&self, c: u8) -> Self::Idx {
-        self.children[c as usize]
+        self.children.get(c as usize).copied().unwrap_or(0)
+    }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        (c as usize..N)
+            .find(|&c| self.children[c] > 0)
+            .map(|c| c as u8)
     }
 }
 
@@ -0,0 +1,50 @@
+use super::{OrNode, ReadNode};
+
+/// A node that has children present in either of its nodes,<br>
+/// where words are only ended if they're ended in exactly one node.
+///
+/// A missing node (`None`) is treated as an empty node, as in [`OrNode`].
+#[derive(Clone, Copy)]
+pub struct XorNode<A, B>(pub(crate) OrNode<A, B>);
+
+impl<A: ReadNode, B: ReadNode> ReadNode for XorNode<A, B> {
+    type Idx = (Option<A::Idx>, Option<B::Idx>);
+
+    fn is_end(&self) -> bool {
+        let OrNode(node0, node1) = &self.0;
+        node0.as_ref().is_some_and(ReadNode::is_end) != node1.as_ref().is_some_and(ReadNode::is_end)
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.0.has(c)
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        self.0.get(c)
+    }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        self.0.next_c(c)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::XorNode;
+    use crate::node::{thin::END_MASK, OrNode, ReadNode, ThinNode};
+
+    #[test]
+    fn end_if_only_one() {
+        let end = ThinNode {
+            idx: 0,
+            mask: END_MASK,
+        };
+        let not_end = ThinNode::default();
+        assert!(XorNode(OrNode(Some(end), Some(not_end))).is_end());
+        assert!(XorNode(OrNode(Some(not_end), Some(end))).is_end());
+        assert!(XorNode(OrNode::<_, ThinNode>(Some(end), None)).is_end());
+        assert!(!XorNode(OrNode(Some(end), Some(end))).is_end());
+        assert!(!XorNode(OrNode(Some(not_end), Some(not_end))).is_end());
+        assert!(!XorNode(OrNode::<ThinNode, ThinNode>(None, None)).is_end());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    utils::{convert::IntoLetters, Alphabet},
+    THIN_CHARS,
+};
+
+/// The symbols written as vowels by [`LetterClasses::vowels`]
+const VOWELS: [&str; 5] = ["a", "e", "i", "o", "u"];
+
+/// Characters that already have a meaning in patterns
+const RESERVED: &str = "-[]^*?{},()|\\";
+
+/// Named classes of letters, which can be written as a single<br>
+/// character in a [`Pattern`](crate::Pattern), i.e. `@` for any vowel.
+///
+/// ```
+/// # use odawg::{LetterClasses, Pattern, ReadDawg, Latin};
+/// let classes = LetterClasses::vowels(&Latin).with('$', "aeiouy");
+/// let pattern = Pattern::parse_with_classes("#$#", &Latin, &classes).unwrap();
+/// assert!(pattern.has("gym"));
+/// assert!(!pattern.has("gmy"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LetterClasses(Vec<(char, u32)>);
+
+impl LetterClasses {
+    /// Creates an empty set of classes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The classes `@`, for the vowels `a`, `e`, `i`, `o` and `u`,<br>
+    /// and `#`, for every other letter in `alphabet`.
+    ///
+    /// These are the classes used by [`Pattern::parse_with`](crate::Pattern::parse_with).
+    pub fn vowels(alphabet: &impl Alphabet) -> Self {
+        let len = alphabet.len().min(THIN_CHARS);
+        let vowels = (0..len as u8)
+            .filter(|&c| alphabet.symbol(c).is_some_and(|s| VOWELS.contains(&s)))
+            .fold(0, |mask, c| mask | (1 << c));
+        let all = u32::MAX.checked_shr(u32::BITS - len as u32).unwrap_or(0);
+        Self(vec![('@', vowels), ('#', all & !vowels)])
+    }
+
+    /// Adds a class named `name` for `letters`,<br>
+    /// replacing any existing class with the same name.
+    ///
+    /// Classes are matched before the symbols of the alphabet,<br>
+    /// so `name` can't be used as a symbol once it names a class.
+    ///
+    /// ## Panics
+    ///
+    /// If `name` already has a meaning in patterns, i.e. `-` or `[`,<br>
+    /// or any letter can't be stored in a [`ThinNode`](crate::ThinNode).
+    pub fn with(mut self, name: char, letters: impl IntoLetters) -> Self {
+        assert!(
+            !RESERVED.contains(name),
+            "'{name}' can't be used to name a class"
+        );
+        let mask = letters.letters().fold(0, |mask, c| {
+            assert!((c as usize) < THIN_CHARS, "letter {c} can't be in a class");
+            mask | (1 << c)
+        });
+
+        match self.0.iter_mut().find(|(c, _)| *c == name) {
+            Some((_, class)) => *class = mask,
+            None => self.0.push((name, mask)),
+        }
+        self
+    }
+
+    /// The letters in the class named `name`, as a mask
+    pub(super) fn get(&self, name: char) -> Option<u32> {
+        self.0
+            .iter()
+            .find(|(c, _)| *c == name)
+            .map(|&(_, mask)| mask)
+    }
+
+    /// The name of the first class with exactly the letters in `mask`
+    pub(super) fn name(&self, mask: u32) -> Option<char> {
+        self.0.iter().find(|(_, m)| *m == mask).map(|&(c, _)| c)
+    }
+
+    /// The characters that should be parsed as special characters,<br>
+    /// made up of the pattern's own `specials` and the class names.
+    pub(super) fn specials(&self, specials: &str) -> String {
+        specials
+            .chars()
+            .chain(self.0.iter().map(|&(c, _)| c))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LetterClasses;
+    use crate::{Latin, Tiles};
+
+    #[test]
+    fn vowels() {
+        let classes = LetterClasses::vowels(&Latin);
+        assert_eq!(classes.get('@'), Some(0b100000100000100010001));
+        assert_eq!(classes.get('#').map(u32::count_ones), Some(21));
+
+        let alphabet = Tiles::new(["b", "a", "ch", "e"]);
+        let classes = LetterClasses::vowels(&alphabet);
+        assert_eq!(classes.get('@'), Some(0b1010));
+        assert_eq!(classes.get('#'), Some(0b0101));
+    }
+
+    #[test]
+    fn with() {
+        let classes = LetterClasses::vowels(&Latin).with('@', "y").with('%', "ab");
+        assert_eq!(classes.get('@'), Some(1 << 24));
+        assert_eq!(classes.get('%'), Some(0b11));
+        assert_eq!(classes.name(0b11), Some('%'));
+        assert_eq!(classes.get('$'), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reserved() {
+        LetterClasses::new().with('^', "a");
+    }
+}
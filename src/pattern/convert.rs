@@ -1,9 +1,12 @@
 use super::Pattern;
-use crate::{FlatDawg, ThinNode, WideNode, THIN_CHARS};
+use crate::{node::END_MASK, FlatDawg, ThinNode, WideNode};
 use std::array;
 
 impl From<Pattern> for FlatDawg<ThinNode> {
     fn from(value: Pattern) -> Self {
+        if value.0.is_empty() {
+            return Self::default();
+        }
         let mut nodes = vec![];
         let mut repeats = 1;
 
@@ -20,7 +23,7 @@ impl From<Pattern> for FlatDawg<ThinNode> {
 
         let end_node = ThinNode {
             idx: 0,
-            mask: 1 << THIN_CHARS,
+            mask: END_MASK,
         };
         for _ in 0..repeats {
             nodes.push(end_node);
@@ -30,21 +33,26 @@ impl From<Pattern> for FlatDawg<ThinNode> {
     }
 }
 
-impl From<Pattern> for FlatDawg<WideNode<THIN_CHARS>> {
+impl<const N: usize> From<Pattern> for FlatDawg<WideNode<N>> {
     fn from(value: Pattern) -> Self {
+        if value.0.is_empty() {
+            return Self::default();
+        }
         let mut nodes = vec![];
 
         for mask in value.0 {
             let next = nodes.len() + 1;
             nodes.push(WideNode {
                 end: false,
-                children: array::from_fn(|i| (mask >> i & 1) as usize * next),
+                children: array::from_fn(|i| {
+                    (mask.checked_shr(i as u32).unwrap_or(0) & 1) as usize * next
+                }),
             });
         }
 
         nodes.push(WideNode {
             end: true,
-            children: [0; THIN_CHARS],
+            children: [0; N],
         });
         Self(nodes)
     }
@@ -70,6 +78,9 @@ mod test {
         for word in thin_dawg.words() {
           assert!(pat.has(&word));
         }
+        assert_eq!(pat.words().count(), pat.len());
+        assert_eq!(thin_dawg.len(), pat.len());
+        assert_eq!(wide_dawg.len(), pat.len());
       }
     }
 
@@ -81,6 +92,8 @@ mod test {
 
         assert!(!thin_dawg.has("jab"));
         assert!(!wide_dawg.has("jab"));
+        assert!(thin_dawg.is_empty());
+        assert!(wide_dawg.is_empty());
     }
 
     #[test]
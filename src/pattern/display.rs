@@ -1,37 +1,76 @@
-use super::Pattern;
-use crate::utils::convert::into_alpha;
+use super::{LetterClasses, Pattern};
+use crate::utils::{Alphabet, Latin};
 use std::fmt::{Display, Write};
 
+/// Displays a [`Pattern`] using the symbols of an alphabet,<br>
+/// see [`Pattern::display`].
+#[derive(Clone, Debug)]
+pub struct PatternDisplay<'a, A> {
+    pattern: &'a Pattern,
+    alphabet: &'a A,
+    classes: LetterClasses,
+}
+
 impl Pattern {
-    fn fmt_range(start: u8, end: u8, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if start == end {
-            return f.write_char(into_alpha(start));
-        }
-        if start == 0 {
-            return write!(f, "-{}", into_alpha(end));
+    /// Displays the pattern with its letters written using `alphabet`,<br>
+    /// using the classes from [`LetterClasses::vowels`].
+    ///
+    /// Multi-character symbols can't always be parsed back the<br>
+    /// same way, i.e. the group `[ch]` displayed for `c` and `h`<br>
+    /// will be parsed as the single symbol `ch` if it exists.<br>
+    /// Letters outside of `alphabet` are written as 2 digits of their index, i.e. `\26`.
+    pub fn display<'a, A: Alphabet>(&'a self, alphabet: &'a A) -> PatternDisplay<'a, A> {
+        self.display_with_classes(alphabet, &LetterClasses::vowels(alphabet))
+    }
+
+    /// Displays the pattern with its letters written using `alphabet`,<br>
+    /// writing any position that matches one of the `classes` as its name.
+    pub fn display_with_classes<'a, A: Alphabet>(
+        &'a self,
+        alphabet: &'a A,
+        classes: &LetterClasses,
+    ) -> PatternDisplay<'a, A> {
+        PatternDisplay {
+            pattern: self,
+            alphabet,
+            classes: classes.clone(),
         }
-        if end == 25 {
-            return write!(f, "{}-", into_alpha(start));
+    }
+
+    /// Helper function to write a single letter, as its index if it's not in `alphabet`
+    fn fmt_letter(c: u8, alphabet: &impl Alphabet, f: &mut impl Write) -> std::fmt::Result {
+        match alphabet.symbol(c) {
+            Some(symbol) => f.write_str(symbol),
+            None => write!(f, "\\{c:02}"),
         }
-        write!(f, "{}-{}", into_alpha(start), into_alpha(end))
     }
 
-    fn fmt_mask(mut mask: u32, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if mask.count_ones() == 0 {
-            return f.write_str("[]");
+    /// Helper function to write a single range, which can only be left<br>
+    /// open at the end if it's the `last` range in the group.
+    fn fmt_range(
+        start: u8,
+        end: u8,
+        last: bool,
+        alphabet: &impl Alphabet,
+        f: &mut impl Write,
+    ) -> std::fmt::Result {
+        if start == end {
+            return Self::fmt_letter(start, alphabet, f);
         }
-        if mask.count_ones() == 1 {
-            return f.write_char(into_alpha(
-                mask.trailing_zeros()
-                    .try_into()
-                    .expect("no more than 255 `0`s in a u32"),
-            ));
+        if start == 0 {
+            f.write_char('-')?;
+            return Self::fmt_letter(end, alphabet, f);
         }
-        if mask.count_ones() == 26 {
-            return f.write_char('-');
+        Self::fmt_letter(start, alphabet, f)?;
+        f.write_char('-')?;
+        if last && end as usize + 1 == alphabet.len() {
+            return Ok(());
         }
+        Self::fmt_letter(end, alphabet, f)
+    }
 
-        f.write_char('[')?;
+    /// Helper function to write the ranges in `mask`, without any brackets
+    fn fmt_ranges(mut mask: u32, alphabet: &impl Alphabet, f: &mut impl Write) -> std::fmt::Result {
         let mut i = 0;
         while mask.count_ones() > 0 {
             let s: u8 = mask
@@ -43,21 +82,137 @@ impl Pattern {
                 .trailing_ones()
                 .try_into()
                 .expect("no more than 255 `1`s in a u32");
-            mask >>= l;
+            mask = mask.checked_shr(l as u32).unwrap_or(0);
 
             i += s;
-            Self::fmt_range(i, i + l - 1, f)?;
+            Self::fmt_range(i, i + l - 1, mask == 0, alphabet, f)?;
             i += l;
         }
-        f.write_char(']')
+        Ok(())
+    }
+
+    /// Writes a single position of the pattern, as a letter, a class<br>
+    /// or whichever of a group or a negated group is shorter.
+    pub(super) fn fmt_mask(
+        mask: u32,
+        alphabet: &impl Alphabet,
+        classes: &LetterClasses,
+        f: &mut impl Write,
+    ) -> std::fmt::Result {
+        if mask.count_ones() == 0 {
+            return f.write_str("[]");
+        }
+        if mask.count_ones() == 1 {
+            return Self::fmt_letter(
+                mask.trailing_zeros()
+                    .try_into()
+                    .expect("no more than 255 `0`s in a u32"),
+                alphabet,
+                f,
+            );
+        }
+        let all = u32::MAX
+            .checked_shr(u32::BITS.saturating_sub(alphabet.len() as u32))
+            .unwrap_or(0);
+        if mask == all {
+            return f.write_char('-');
+        }
+        if let Some(name) = classes.name(mask) {
+            return f.write_char(name);
+        }
+
+        let mut group = String::new();
+        Self::fmt_ranges(mask, alphabet, &mut group)?;
+
+        // letters outside of the alphabet can't be negated
+        let mut negated = String::new();
+        if mask & !all == 0 {
+            Self::fmt_ranges(!mask & all, alphabet, &mut negated)?;
+        }
+
+        // the `^` counts towards the length, so ties are left as groups
+        if !negated.is_empty() && negated.chars().count() < group.chars().count() - 1 {
+            return write!(f, "[^{negated}]");
+        }
+        write!(f, "[{group}]")
     }
 }
 
-impl Display for Pattern {
+impl<A: Alphabet> Display for PatternDisplay<'_, A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for &mask in &self.0 {
-            Self::fmt_mask(mask, f)?;
+        for &mask in &self.pattern.0 {
+            Pattern::fmt_mask(mask, self.alphabet, &self.classes, f)?;
         }
         Ok(())
     }
 }
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display(&Latin).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LetterClasses, Pattern};
+    use crate::{utils::fixtures::german, Latin, Tiles, THIN_CHARS};
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn tiles_round_trip(masks in prop::collection::vec(0..(1u32 << 30), 0..20)) {
+        let alphabet = german();
+        let pat = Pattern(masks);
+        let s = pat.display(&alphabet).to_string();
+        assert_eq!(Pattern::parse_with(&s, &alphabet).unwrap(), pat, "{}", s);
+      }
+
+      #[test]
+      fn outside_round_trip(masks in prop::collection::vec(0..(1u32 << THIN_CHARS), 0..20)) {
+        let pat = Pattern(masks);
+        let s = pat.to_string();
+        assert_eq!(s.parse::<Pattern>().unwrap(), pat, "{}", s);
+        let s = pat.display(&german()).to_string();
+        assert_eq!(Pattern::parse_with(&s, &german()).unwrap(), pat, "{}", s);
+      }
+    }
+
+    #[test]
+    fn tiles_ranges() {
+        let alphabet = german();
+        let pat = Pattern::parse_with("[ä-]-[-d]", &alphabet).unwrap();
+        assert_eq!(pat.0, [0b1111 << 26, (1 << 30) - 1, 0b1111]);
+        assert_eq!(pat.display(&alphabet).to_string(), "[ä-]-[-d]");
+        assert_eq!(pat.to_string(), r"[\26-\29][-\29][-d]");
+        assert_eq!(Pattern(vec![1 << 30]).to_string(), r"\30");
+        assert_eq!(
+            Pattern(vec![1 << 30])
+                .display(&Tiles::new(["a"]))
+                .to_string(),
+            r"\30"
+        );
+        assert_eq!(
+            Pattern(vec![1 << 3])
+                .display(&Tiles::new(["a"]))
+                .to_string(),
+            r"\03"
+        );
+    }
+
+    #[test]
+    fn negation_and_classes() {
+        let pat: Pattern = "[^e][aeiou][b-df-hj-np-tv-z][^aeiou]".parse().unwrap();
+        assert_eq!(pat.to_string(), "[^e]@##");
+        let pat: Pattern = "[a-df-][ab]".parse().unwrap();
+        assert_eq!(pat.to_string(), "[^e][-b]");
+
+        let classes = LetterClasses::new().with('%', "ab");
+        let s = pat.display_with_classes(&Latin, &classes).to_string();
+        assert_eq!(s, "[^e]%");
+        assert_eq!(
+            Pattern::parse_with_classes(&s, &Latin, &classes).unwrap(),
+            pat
+        );
+    }
+}
@@ -1,8 +1,8 @@
-use crate::node::ReadNode;
-use crate::utils::convert::into_alpha;
-use crate::utils::convert::ALPHA_CHARS;
+use super::{LetterClasses, Pattern};
+use crate::node::{ReadNode, CHILD_MASK};
+use crate::utils::Latin;
+use crate::THIN_CHARS;
 use std::fmt::Display;
-use std::fmt::Write;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Node<'a> {
@@ -19,70 +19,15 @@ impl<'a> Letter<'a> {
     }
 }
 
-impl Letter<'_> {
-    pub(crate) fn fmt_range(
-        start: u8,
-        end: u8,
-        f: &mut std::fmt::Formatter<'_>,
-    ) -> std::fmt::Result {
-        if start == end {
-            return f.write_char(into_alpha(start));
-        }
-        if start == 0 {
-            return write!(f, "-{}", into_alpha(end));
-        }
-        if end == 25 {
-            return write!(f, "{}-", into_alpha(start));
-        }
-        write!(f, "{}-{}", into_alpha(start), into_alpha(end))
-    }
-}
-
 impl Display for Letter<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let Some(node) = self.0 else {
             return Ok(());
         };
-        let mut mask = *node.mask;
-
-        if mask.count_ones() == 0 {
-            return f.write_str("[]");
-        }
-        if mask.count_ones() == 1 {
-            return f.write_char(into_alpha(
-                mask.trailing_zeros()
-                    .try_into()
-                    .expect("no more than 255 `0`s in a u32"),
-            ));
-        }
-        if mask.count_ones() == 26 {
-            return f.write_char('-');
-        }
-
-        f.write_char('[')?;
-        let mut i = 0;
-        while mask.count_ones() > 0 {
-            let s: u8 = mask
-                .trailing_zeros()
-                .try_into()
-                .expect("no more than 255 `0`s in a u32");
-            mask >>= s;
-            let l: u8 = mask
-                .trailing_ones()
-                .try_into()
-                .expect("no more than 255 `1`s in a u32");
-            mask >>= l;
-
-            i += s;
-            Self::fmt_range(i, i + l - 1, f)?;
-            i += l;
-        }
-        f.write_char(']')
+        Pattern::fmt_mask(*node.mask, &Latin, &LetterClasses::vowels(&Latin), f)
     }
 }
 
-pub const CHILD_MASK: u32 = (1 << ALPHA_CHARS) - 1;
-
 impl ReadNode for Letter<'_> {
     type Idx = usize;
 
@@ -94,19 +39,18 @@ impl ReadNode for Letter<'_> {
     }
 
     fn has(&self, i: u8) -> bool {
-        self.0.is_some_and(|node| (node.mask >> i) & 1 > 0)
+        (i as usize) < THIN_CHARS && self.0.is_some_and(|node| (node.mask >> i) & 1 > 0)
     }
 
     fn get(&self, i: u8) -> Self::Idx {
-        self.0.map_or(
-            0,
-            |Node { mask, next }| if (mask >> i) & 1 > 0 { next } else { 0 },
-        )
+        self.0
+            .filter(|_| self.has(i))
+            .map_or(0, |Node { next, .. }| next)
     }
 
     fn next_c(&self, c: u8) -> Option<u8> {
         let Node { mask, .. } = self.0?;
-        let masked = mask & ((1 << c) - 1);
-        (masked > 0).then(|| masked.count_zeros() as u8)
+        let masked = mask & CHILD_MASK & u32::MAX.checked_shl(c as u32)?;
+        (masked > 0).then(|| masked.trailing_zeros() as u8)
     }
 }
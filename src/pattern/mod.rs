@@ -1,15 +1,117 @@
-use crate::{dawg::IndexDawg, utils::convert::IntoLetters, ReadDawg};
+use crate::{
+    dawg::IndexDawg,
+    node::CHILD_MASK,
+    utils::{convert::TryIntoLetters, Alphabet},
+    ReadDawg,
+};
 use serde::{Deserialize, Serialize};
 
 mod letter;
-use letter::{Letter, Node, CHILD_MASK};
+use letter::{Letter, Node};
+mod classes;
+pub use classes::LetterClasses;
 mod convert;
 mod display;
+pub use display::PatternDisplay;
 mod parse;
+pub use parse::{ErrorKind, ParseError};
+mod shape;
+pub use shape::ShapePattern;
+mod var;
+pub use var::VarPattern;
 
 /// A word-like pattern, defined by the acceptable letters at each index.
+///
+/// Patterns are parsed from strings, where each position is either:
+/// - a letter, i.e. `c`, only matching that letter
+/// - `-`, matching any letter
+/// - a group, i.e. `[ab]` or `[r-t]`, matching any of the letters or ranges in it.<br>
+///   Ranges can be left open, i.e. `[-c]` or `[x-]`, to start or end with the alphabet.<br>
+///   Groups starting with `^`, i.e. `[^est]`, match any letter not in the rest of the group.
+/// - a class, i.e. `@` for any vowel or `#` for any consonant, see [`LetterClasses`].<br>
+///   Classes can also be used in groups, i.e. `[#y]`.
+/// - `\` followed by a letter's index, i.e. `\26`, for letters outside of the alphabet.
+///
+/// The empty pattern matches no words, not even the empty word.
+///
+/// ```
+/// # use odawg::{ErrorKind, Pattern, ReadDawg};
+/// let pattern: Pattern = "[bc]a[r-t]".parse().unwrap();
+/// assert_eq!(pattern.len(), 6);
+/// assert!(pattern.has("cat"));
+/// assert!(!pattern.has("cab"));
+///
+/// let pattern: Pattern = "#@[^est]".parse().unwrap();
+/// assert!(pattern.has("cab"));
+/// assert!(!pattern.has("cat"));
+/// assert!(!pattern.has("oak"));
+///
+/// let err = "c[at".parse::<Pattern>().unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnclosedGroup);
+/// assert_eq!(err.span(), 4..4);
+/// ```
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Pattern(Vec<u32>);
+pub struct Pattern(pub(crate) Vec<u32>);
+
+impl Pattern {
+    /// The pattern matching the letters allowed by both patterns at each position,<br>
+    /// or `None` if the patterns have different lengths.
+    ///
+    /// ```
+    /// # use odawg::{Pattern, ReadDawg};
+    /// let pattern0: Pattern = "[bh]a-".parse().unwrap();
+    /// let pattern1: Pattern = "-[ao][rt]".parse().unwrap();
+    /// assert_eq!(pattern0.and(&pattern1).unwrap().to_string(), "[bh]a[rt]");
+    /// ```
+    pub fn and(&self, other: &Pattern) -> Option<Pattern> {
+        self.zip_with(other, |m0, m1| m0 & m1)
+    }
+
+    /// The pattern matching the letters allowed by either pattern at each position,<br>
+    /// or `None` if the patterns have different lengths.
+    ///
+    /// This matches every word either pattern does, but can also match more,<br>
+    /// i.e. combining `hat` and `bar` also matches `bat` and `har`.
+    ///
+    /// ```
+    /// # use odawg::{Pattern, ReadDawg};
+    /// let pattern0: Pattern = "hat".parse().unwrap();
+    /// let pattern1: Pattern = "bar".parse().unwrap();
+    /// assert_eq!(pattern0.or(&pattern1).unwrap().to_string(), "[bh]a[rt]");
+    /// ```
+    pub fn or(&self, other: &Pattern) -> Option<Pattern> {
+        self.zip_with(other, |m0, m1| m0 | m1)
+    }
+
+    /// Helper function to combine the masks at each position with `f`
+    fn zip_with(&self, other: &Pattern, f: impl Fn(u32, u32) -> u32) -> Option<Pattern> {
+        (self.0.len() == other.0.len()).then(|| {
+            Pattern(
+                self.0
+                    .iter()
+                    .zip(&other.0)
+                    .map(|(&m0, &m1)| f(m0, m1))
+                    .collect(),
+            )
+        })
+    }
+
+    /// The pattern matching the letters of `alphabet` that aren't<br>
+    /// allowed by this pattern at each position.
+    ///
+    /// This needs a different letter at every position, so some words match<br>
+    /// neither pattern, i.e. `cot` matches neither `cat` nor `[^c][^a][^t]`.
+    ///
+    /// ```
+    /// # use odawg::{Latin, Pattern, ReadDawg};
+    /// let pattern: Pattern = "[^a-c]@".parse().unwrap();
+    /// assert_eq!(pattern.complement(&Latin).to_string(), "[-c]#");
+    /// ```
+    pub fn complement(&self, alphabet: &impl Alphabet) -> Pattern {
+        let all = Self::range_mask(0, alphabet.len().saturating_sub(1) as u8);
+        Pattern(self.0.iter().map(|mask| !mask & all).collect())
+    }
+}
 
 impl IndexDawg for Pattern {
     type Idx = usize;
@@ -17,6 +119,10 @@ impl IndexDawg for Pattern {
 
     type NodeRef<'a> = Letter<'a>;
     fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        // the empty pattern matches no words, not even the empty word
+        if self.0.is_empty() {
+            return Letter::new(&0, 0);
+        }
         Letter((idx < self.0.len()).then(|| Node {
             mask: &self.0[idx],
             next: idx + 1,
@@ -25,6 +131,11 @@ impl IndexDawg for Pattern {
 }
 
 impl ReadDawg for Pattern {
+    /// All the words matching this pattern, in ascending lexicographic order
+    fn words(&self) -> impl Iterator<Item = Vec<u8>> {
+        self.words_sorted()
+    }
+
     fn is_empty(&self) -> bool {
         if self.0.is_empty() {
             return true;
@@ -42,17 +153,20 @@ impl ReadDawg for Pattern {
             .product()
     }
 
-    fn has(&self, word: impl IntoLetters) -> bool {
-        let mut letters = word.letters();
+    fn has(&self, word: impl TryIntoLetters) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+        let mut letters = word.try_letters();
 
         // any of masks doesn't contain letter => fail
         for mask in self.0.iter() {
             // iterators should have same length
             // => we should be able to fetch a letter
-            let Some(c) = letters.next() else {
+            let Some(Ok(c)) = letters.next() else {
                 return false;
             };
-            if (mask & (1 << c)) == 0 {
+            if (mask & CHILD_MASK).checked_shr(c as u32).unwrap_or(0) & 1 == 0 {
                 return false;
             }
         }
@@ -66,10 +180,11 @@ impl ReadDawg for Pattern {
 #[cfg(test)]
 mod strategies {
     use super::*;
+    use crate::LATIN_CHARS;
     use prop::{bits::u32::sampled, collection::vec};
     use proptest::prelude::*;
 
-    const ALPHA_MASK: u32 = (1 << 26) - 1;
+    const ALPHA_MASK: u32 = (1 << LATIN_CHARS) - 1;
 
     /// Generates an arbitrary pattern.
     pub fn pattern() -> BoxedStrategy<Pattern> {
@@ -100,3 +215,97 @@ mod strategies {
             .boxed()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{strategies::pattern_len, Pattern};
+    use crate::{dawg::IndexDawg, from_word, into_word, Latin, ReadDawg, ReadNode, THIN_CHARS};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn next_c_matches(masks in vec(any::<u32>(), 1..2), c in 0..=THIN_CHARS as u8) {
+        let pattern = Pattern(masks);
+        let node = pattern.index(0);
+        let next = (c..THIN_CHARS as u8).find(|&c| node.has(c));
+        assert_eq!(node.next_c(c), next);
+      }
+
+      #[test]
+      fn letter_set_words(
+        masks0 in vec(0..(1u32 << 26), 3),
+        masks1 in vec(0..(1u32 << 26), 3),
+        word in vec(0..26u8, 3),
+      ) {
+        let (pattern0, pattern1) = (Pattern(masks0), Pattern(masks1));
+        let (has0, has1) = (pattern0.has(&word), pattern1.has(&word));
+        assert_eq!(pattern0.and(&pattern1).unwrap().has(&word), has0 && has1);
+        assert!(pattern0.or(&pattern1).unwrap().has(&word) >= (has0 || has1));
+        assert!(!(has0 && pattern0.complement(&Latin).has(&word)));
+      }
+
+      #[test]
+      fn words_are_product(pat in pattern_len(10_000)) {
+        let words: Vec<_> = pat.words().collect();
+        assert_eq!(words.len(), pat.len());
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+        for word in &words {
+          assert!(pat.has(word));
+        }
+      }
+    }
+
+    #[test]
+    fn words_in_order() {
+        let pattern: Pattern = "[bc]a[tr]".parse().unwrap();
+        let words: Vec<_> = pattern.words().map(into_word).collect();
+        assert_eq!(words, ["bar", "bat", "car", "cat"]);
+
+        let words: Vec<_> = pattern.words_with_prefix("ca").map(into_word).collect();
+        assert_eq!(words.len(), 2);
+        assert!(pattern.has_prefix("ca"));
+        assert!(!pattern.has_prefix("da"));
+        assert_eq!(pattern.rank("car"), Some(2));
+        assert_eq!(pattern.select(3), Some(from_word("cat")));
+    }
+
+    #[test]
+    fn empty() {
+        let pattern = Pattern(vec![]);
+        assert_eq!(pattern.words().count(), 0);
+        assert_eq!(pattern.len(), 0);
+        assert!(pattern.is_empty());
+        assert!(!pattern.has(""));
+        assert!(!pattern.has_prefix(""));
+    }
+
+    #[test]
+    fn empty_letter() {
+        let pattern = Pattern(vec![0b100, 0b1, 0]);
+        assert_eq!(pattern.words().count(), 0);
+        assert!(!pattern.has_prefix("ca"));
+        assert!(pattern.is_empty());
+    }
+
+    #[test]
+    fn letter_sets() {
+        let pattern0: Pattern = "c[ao]t".parse().unwrap();
+        let pattern1: Pattern = "[bc]@[rt]".parse().unwrap();
+        assert_eq!(pattern0.and(&pattern1).unwrap().to_string(), "c[ao]t");
+        assert_eq!(pattern0.or(&pattern1).unwrap().to_string(), "[b-c]@[rt]");
+
+        let complement = pattern0.complement(&Latin);
+        assert_eq!(complement.to_string(), "[^c][^ao][^t]");
+        assert_eq!(complement.complement(&Latin), pattern0);
+    }
+
+    #[test]
+    fn letter_set_lengths() {
+        let pattern0: Pattern = "cat".parse().unwrap();
+        let pattern1: Pattern = "ca".parse().unwrap();
+        assert_eq!(pattern0.and(&pattern1), None);
+        assert_eq!(pattern0.or(&pattern1), None);
+        assert_eq!(pattern0.or(&Pattern(vec![])), None);
+    }
+}
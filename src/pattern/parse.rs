@@ -1,10 +1,30 @@
-use super::Pattern;
-use crate::utils::convert::from_alpha;
-use std::{fmt::Debug, str::FromStr};
+use super::{LetterClasses, Pattern};
+use crate::{
+    utils::{Alphabet, Latin},
+    THIN_CHARS,
+};
+use std::{
+    fmt::{Debug, Display},
+    ops::Range,
+    str::FromStr,
+};
 
+/// An error for when a string can't be parsed as a [`Pattern`]<br>
+/// or a [`VarPattern`](crate::VarPattern).
+///
+/// This keeps a copy of the input, so that it can be displayed<br>
+/// with the position of the error marked underneath, i.e.
+///
+/// ```text
+/// Group was not closed
+///
+/// c[ab
+///     ^
+/// ```
+#[derive(Clone, PartialEq, Eq)]
 pub struct ParseError {
     input: Box<str>,
-    idx: usize,
+    span: Range<usize>,
     kind: ErrorKind,
 }
 
@@ -12,9 +32,50 @@ pub struct ParseError {
 const VIEW_SIZE: usize = 50;
 const VIEW_HALF: usize = VIEW_SIZE / 2;
 
-impl Debug for ParseError {
+impl ParseError {
+    /// Helper function for an error at the character starting at byte `idx`,<br>
+    /// or at the end of the input if there are no characters left.
+    pub(super) fn new(input: &str, idx: usize, kind: ErrorKind) -> Self {
+        let len = input[idx..].chars().next().map_or(0, char::len_utf8);
+        Self {
+            input: input.into(),
+            span: idx..idx + len,
+            kind,
+        }
+    }
+
+    /// Helper function for an error covering the bytes in `span`
+    pub(super) fn spanning(input: &str, span: Range<usize>, kind: ErrorKind) -> Self {
+        Self {
+            input: input.into(),
+            span,
+            kind,
+        }
+    }
+
+    /// What went wrong when parsing the pattern
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The range of bytes in the input the error occured at.<br>
+    /// This is empty if the error occured at the end of the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The string that was being parsed
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (mut ps, mut pe) = (self.idx, self.input.len() - self.idx);
+        // the view is measured in characters, so that multi-byte
+        // characters are never split and the marker lines up
+        let (before, after) = self.input.split_at(self.span.start);
+        let (mut ps, mut pe) = (before.chars().count(), after.chars().count());
         (ps, pe) = match () {
             () if (ps < VIEW_HALF) & (pe < VIEW_HALF) => (ps, pe),
             () if (ps < VIEW_HALF) => (ps, VIEW_SIZE - ps),
@@ -22,135 +83,275 @@ impl Debug for ParseError {
             () => (VIEW_HALF, VIEW_HALF),
         };
 
-        writeln!(f, "{:?}\n", self.kind)?;
-        writeln!(f, "{}", &self.input[self.idx - ps..self.idx + pe])?;
-        writeln!(f, "{}", " ".repeat(ps) + "^")
+        let start = before
+            .char_indices()
+            .rev()
+            .take(ps)
+            .last()
+            .map_or(before.len(), |(i, _)| i);
+        let end = after.char_indices().nth(pe).map_or(after.len(), |(i, _)| i);
+        let marks = self.input[self.span.clone()].chars().count().max(1);
+
+        writeln!(f, "{}\n", self.kind)?;
+        writeln!(f, "{}{}", &before[start..], &after[..end])?;
+        write!(f, "{}{}", " ".repeat(ps), "^".repeat(marks))
+    }
+}
+
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{self}")
     }
 }
 
+impl std::error::Error for ParseError {}
+
+/// The kinds of errors that can occur when parsing a [`Pattern`]<br>
+/// or a [`VarPattern`](crate::VarPattern)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ErrorKind {
+    /// A group was opened with `[` but never closed,<br>
+    /// or another group was opened inside of it
     UnclosedGroup,
+    /// A `]` was found outside of a group
     ReclosedGroup,
+    /// A range in a group was followed by another `-`
     UnclosedRange,
+    /// A range in a group ends with a letter before its start, i.e. `[t-r]`
+    ReversedRange,
+    /// A character was found that isn't in the alphabet
     Unexpected,
+    /// A bracket was opened with `(` but never closed
+    UnclosedBracket,
+    /// A `)` was found outside of a bracket
+    ReclosedBracket,
+    /// A `?` or `{` was found with nothing before it to repeat
+    NothingToRepeat,
+    /// A repetition wasn't of the form `{m}`, `{m,}`, `{,n}` or `{m,n}`<br>
+    /// with `m <= n`, or was never closed
+    InvalidRepeat,
 }
 
-impl Debug for ErrorKind {
+impl Display for ErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let message = match self {
             Self::UnclosedGroup => "Group was not closed",
             Self::ReclosedGroup => "Closed non-existant group",
             Self::UnclosedRange => "Range was not closed with a character",
+            Self::ReversedRange => "Range ends before it starts",
             Self::Unexpected => "Unexpected character in Pattern",
+            Self::UnclosedBracket => "Bracket was not closed",
+            Self::ReclosedBracket => "Closed non-existant bracket",
+            Self::NothingToRepeat => "Nothing to repeat",
+            Self::InvalidRepeat => "Invalid repetition",
         };
         write!(f, "{message}")
     }
 }
 
+/// A single token of a pattern, either a letter or a special character
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Token {
+    Letter(u8),
+    Char(char),
+}
+
+/// An iterator over the tokens of a pattern and their byte offsets.
+///
+/// The `specials` are always parsed as characters,<br>
+/// even if they are also symbols of the alphabet.<br>
+/// A `\` followed by up to 2 digits, i.e. `\26`, is the letter with that index.
+pub(super) struct Tokens<'a, A> {
+    alphabet: &'a A,
+    input: &'a str,
+    specials: &'a str,
+    idx: usize,
+}
+
+impl<'a, A> Tokens<'a, A> {
+    pub(super) fn new(alphabet: &'a A, input: &'a str, specials: &'a str) -> Self {
+        Self {
+            alphabet,
+            input,
+            specials,
+            idx: 0,
+        }
+    }
+}
+
+impl<A> Tokens<'_, A> {
+    /// Helper function to parse a letter written as its index, i.e. `\26`,<br>
+    /// or the `\` on its own if it isn't followed by a letter's index.
+    fn escaped(rest: &str) -> (Token, usize) {
+        let digits = rest[1..]
+            .bytes()
+            .take(2)
+            .take_while(u8::is_ascii_digit)
+            .count();
+        match rest[1..=digits].parse::<u8>() {
+            Ok(v) if (v as usize) < THIN_CHARS => (Token::Letter(v), 1 + digits),
+            _ => (Token::Char('\\'), 1),
+        }
+    }
+}
+
+impl<A: Alphabet> Iterator for Tokens<'_, A> {
+    type Item = (usize, Token);
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.idx;
+        let rest = &self.input[idx..];
+        let c = rest.chars().next()?;
+
+        let (token, len) = match self.alphabet.parse(rest) {
+            _ if c == '\\' => Self::escaped(rest),
+            Some((v, len)) if !self.specials.contains(c) => (Token::Letter(v), len),
+            _ => (Token::Char(c), c.len_utf8()),
+        };
+        self.idx += len;
+        Some((idx, token))
+    }
+}
+
 impl Pattern {
-    fn range_mask(s: u8, e: u8) -> u32 {
+    pub(super) fn range_mask(s: u8, e: u8) -> u32 {
         (1 << (e + 1)) - (1 << s)
     }
 
-    fn parse_group(
+    pub(super) fn parse_group(
         s: &str,
-        chars: &mut impl Iterator<Item = (usize, char)>,
+        last: u8,
+        classes: &LetterClasses,
+        tokens: &mut impl Iterator<Item = (usize, Token)>,
     ) -> Result<u32, ParseError> {
-        let input = s.into();
         let mut mask = 0;
         let mut start = None;
+        let mut start_idx = 0;
         let mut is_range = false;
+        let mut negate = false;
+        // the start of a reversed range, reported once the end letter is complete
+        let mut reversed = None;
 
-        for (idx, c) in chars {
-            match c {
-                'a'..='z' if is_range => {
-                    let v = from_alpha(c);
-                    let s = start.replace(v).unwrap_or(0);
-                    mask |= Self::range_mask(s, v);
+        for (i, (idx, token)) in tokens.enumerate() {
+            if let Some(start_idx) = reversed {
+                return Err(ParseError::spanning(
+                    s,
+                    start_idx..idx,
+                    ErrorKind::ReversedRange,
+                ));
+            }
+            match token {
+                Token::Char('^') if i == 0 => negate = true,
+                Token::Letter(v) if is_range => {
+                    let c = start.replace(v).unwrap_or(0);
+                    if c > v {
+                        reversed = Some(start_idx);
+                        continue;
+                    }
+                    mask |= Self::range_mask(c, v);
                     is_range = false;
                 }
-                'a'..='z' => {
-                    let v = from_alpha(c);
+                Token::Letter(v) => {
+                    start_idx = idx;
                     if let Some(v) = start.replace(v) {
                         mask |= Self::range_mask(v, v);
                     }
                 }
-                '-' if !is_range => is_range = true,
+                Token::Char('-') if !is_range => is_range = true,
 
                 // end of group, cleanup previous char/range
-                ']' => {
+                Token::Char(']') => {
                     if is_range {
-                        mask |= Self::range_mask(start.unwrap_or(0), 25);
+                        // open ranges end at the last letter of the alphabet
+                        let c = start.unwrap_or(0);
+                        if c > last {
+                            let span = start_idx..idx;
+                            return Err(ParseError::spanning(s, span, ErrorKind::ReversedRange));
+                        }
+                        mask |= Self::range_mask(c, last);
                     } else if let Some(v) = start {
                         mask |= Self::range_mask(v, v);
                     }
+                    if negate {
+                        mask = !mask & Self::range_mask(0, last);
+                    }
                     return Ok(mask);
                 }
 
                 // error state handling
-                '-' if is_range => {
-                    return Err(ParseError {
-                        input,
-                        idx,
-                        kind: ErrorKind::UnclosedRange,
-                    })
-                }
-                '[' => {
-                    return Err(ParseError {
-                        input,
-                        idx,
-                        kind: ErrorKind::UnclosedGroup,
-                    })
-                }
-                _ => {
-                    return Err(ParseError {
-                        input,
-                        idx,
-                        kind: ErrorKind::Unexpected,
-                    })
+                Token::Char('-') => return Err(ParseError::new(s, idx, ErrorKind::UnclosedRange)),
+                Token::Char('[') => return Err(ParseError::new(s, idx, ErrorKind::UnclosedGroup)),
+                Token::Char(c) => {
+                    let Some(class) = classes.get(c) else {
+                        return Err(ParseError::new(s, idx, ErrorKind::Unexpected));
+                    };
+                    if is_range {
+                        return Err(ParseError::new(s, idx, ErrorKind::UnclosedRange));
+                    }
+                    if let Some(v) = start.take() {
+                        mask |= Self::range_mask(v, v);
+                    }
+                    mask |= class;
                 }
             }
         }
 
-        Err(ParseError {
-            input,
-            idx: s.len(),
-            kind: ErrorKind::UnclosedGroup,
-        })
+        if let Some(start_idx) = reversed {
+            return Err(ParseError::spanning(
+                s,
+                start_idx..s.len(),
+                ErrorKind::ReversedRange,
+            ));
+        }
+        Err(ParseError::new(s, s.len(), ErrorKind::UnclosedGroup))
     }
-}
 
-impl FromStr for Pattern {
-    type Err = ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let input = s.into();
+    /// Parses a pattern whose letters are written with `alphabet`,<br>
+    /// using the classes from [`LetterClasses::vowels`].
+    ///
+    /// Multi-character symbols are parsed greedily, so in a group<br>
+    /// like `[ch]` a `ch` symbol is preferred over `c` and `h`.
+    ///
+    /// ## Panics
+    ///
+    /// If `alphabet` has more than [`THIN_CHARS`](crate::THIN_CHARS) letters.
+    pub fn parse_with(s: &str, alphabet: &impl Alphabet) -> Result<Self, ParseError> {
+        Self::parse_with_classes(s, alphabet, &LetterClasses::vowels(alphabet))
+    }
+
+    /// Parses a pattern whose letters are written with `alphabet`,<br>
+    /// where each of the `classes` can be used in place of a letter.
+    ///
+    /// ## Panics
+    ///
+    /// If `alphabet` has more than [`THIN_CHARS`](crate::THIN_CHARS) letters.
+    pub fn parse_with_classes(
+        s: &str,
+        alphabet: &impl Alphabet,
+        classes: &LetterClasses,
+    ) -> Result<Self, ParseError> {
+        assert!(
+            alphabet.len() <= THIN_CHARS,
+            "Patterns support at most {THIN_CHARS} letters"
+        );
+        let last = alphabet.len().saturating_sub(1) as u8;
         let mut masks = vec![];
 
-        let mut chars = s.chars().enumerate();
-        while let Some((idx, c)) = chars.next() {
-            match c {
-                'a'..='z' => {
-                    let v = from_alpha(c);
-                    masks.push(Self::range_mask(v, v));
+        let specials = classes.specials("-[]^");
+        let mut tokens = Tokens::new(alphabet, s, &specials);
+        while let Some((idx, token)) = tokens.next() {
+            match token {
+                Token::Letter(v) => masks.push(Self::range_mask(v, v)),
+                Token::Char('-') => masks.push(Self::range_mask(0, last)),
+                Token::Char('[') => {
+                    masks.push(Self::parse_group(s, last, classes, &mut tokens)?);
                 }
-                '-' => masks.push(Self::range_mask(0, 25)),
-                '[' => masks.push(Self::parse_group(s, &mut chars)?),
 
                 // error state handling
-                ']' => {
-                    return Err(ParseError {
-                        input,
-                        idx,
-                        kind: ErrorKind::ReclosedGroup,
-                    })
-                }
-                _ => {
-                    return Err(ParseError {
-                        input,
-                        idx,
-                        kind: ErrorKind::Unexpected,
-                    })
-                }
+                Token::Char(']') => return Err(ParseError::new(s, idx, ErrorKind::ReclosedGroup)),
+                Token::Char(c) => match classes.get(c) {
+                    Some(class) => masks.push(class),
+                    None => return Err(ParseError::new(s, idx, ErrorKind::Unexpected)),
+                },
             }
         }
 
@@ -158,11 +359,22 @@ impl FromStr for Pattern {
     }
 }
 
+impl FromStr for Pattern {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Latin)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::ReadDawg;
 
-    use super::{super::strategies::pattern, Pattern};
+    use super::{super::strategies::pattern, ErrorKind, LetterClasses, Pattern};
+    use crate::{
+        utils::{fixtures::welsh, Alphabet},
+        Latin,
+    };
     use proptest::prelude::*;
 
     proptest! {
@@ -172,6 +384,16 @@ mod test {
         let pat_: Pattern = s.parse().unwrap();
         assert_eq!(pat, pat_, "{} != {}", pat, pat_);
       }
+
+      #[test]
+      fn any_input(s in "\\PC{0,80}") {
+        if let Err(err) = s.parse::<Pattern>() {
+          // spans should always lie on character boundaries
+          let span = &s[err.span()];
+          assert!(span.chars().count() <= 1);
+          let _ = err.to_string();
+        }
+      }
     }
 
     #[test]
@@ -291,4 +513,134 @@ mod test {
         assert!(pat.has("zap"));
         assert!(!pat.has("bat"));
     }
+
+    #[test]
+    fn tiles() {
+        let alphabet = welsh();
+        let pat = Pattern::parse_with("[chdd]-wr", &alphabet).unwrap();
+        assert_eq!(pat.len(), 2 * 29);
+        assert!(pat.has(alphabet.spell("chwwr")));
+        assert!(pat.has(alphabet.spell("ddywr")));
+        assert!(!pat.has(alphabet.spell("cwwr")));
+        assert!(!pat.has(alphabet.spell("chwr")));
+    }
+
+    #[test]
+    fn tiles_range() {
+        let alphabet = welsh();
+        let pat = Pattern::parse_with("[th-][-b]", &alphabet).unwrap();
+        assert_eq!(pat.len(), 4 * 2);
+        assert!(pat.has(alphabet.spell("tha")));
+        assert!(pat.has(alphabet.spell("yb")));
+        assert!(!pat.has(alphabet.spell("tb")));
+        assert_eq!(pat.display(&alphabet).to_string(), "[th-][-b]");
+    }
+
+    #[test]
+    fn tiles_unexpected() {
+        assert!(Pattern::parse_with("cat", &welsh()).is_ok());
+        assert!(Pattern::parse_with("kat", &welsh()).is_err());
+        assert!("ñu".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn escaped() {
+        let pat: Pattern = r"a\02[\26-\30]\7".parse().unwrap();
+        assert_eq!(pat.0, [1, 1 << 2, 0b11111 << 26, 1 << 7]);
+        let kind = |s: &str| s.parse::<Pattern>().unwrap_err().kind();
+        assert_eq!(kind(r"\"), ErrorKind::Unexpected);
+        assert_eq!(kind(r"\a"), ErrorKind::Unexpected);
+        assert_eq!(r"\31".parse::<Pattern>().unwrap_err().span(), 0..1);
+        assert_eq!(r"\261".parse::<Pattern>().unwrap_err().span(), 3..4);
+    }
+
+    #[test]
+    fn errors() {
+        let kind = |s: &str| s.parse::<Pattern>().unwrap_err().kind();
+        assert_eq!(kind("c[at"), ErrorKind::UnclosedGroup);
+        assert_eq!(kind("c[a[t]"), ErrorKind::UnclosedGroup);
+        assert_eq!(kind("ca]t"), ErrorKind::ReclosedGroup);
+        assert_eq!(kind("c[a--]"), ErrorKind::UnclosedRange);
+        assert_eq!(kind("cAt"), ErrorKind::Unexpected);
+        assert_eq!(kind("[t-r]"), ErrorKind::ReversedRange);
+        assert_eq!(kind("[a-aq-p"), ErrorKind::ReversedRange);
+
+        let err = "c[at-r]".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.span(), 3..6);
+        assert_eq!(
+            err.to_string(),
+            "Range ends before it starts\n\nc[at-r]\n   ^^^"
+        );
+        let err = Pattern::parse_with("[th-ch]", &welsh()).unwrap_err();
+        assert_eq!(err.span(), 1..6);
+        let err = r"[\30-\26]".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.span(), 1..8);
+        let err = r"[\30-]".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.span(), 1..5);
+
+        let err = "ca]t".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.span(), 2..3);
+        assert_eq!(err.input(), "ca]t");
+        assert_eq!(err.to_string(), "Closed non-existant group\n\nca]t\n  ^");
+    }
+
+    #[test]
+    fn multi_byte_errors() {
+        let err = "ñu".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.span(), 0..2);
+        assert_eq!(err.to_string(), "Unexpected character in Pattern\n\nñu\n^");
+
+        let err = "ça]t".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        let err = "ab]ça".parse::<Pattern>().unwrap_err();
+        assert_eq!(err.to_string(), "Closed non-existant group\n\nab]ça\n  ^");
+
+        // the view is cut to 50 characters, not bytes
+        let input = "ab".repeat(20) + "]" + &"é".repeat(40);
+        let err = input.parse::<Pattern>().unwrap_err();
+        assert_eq!(err.span(), 40..41);
+        let view = "b".to_string() + &"ab".repeat(12) + "]" + &"é".repeat(24);
+        let marker = " ".repeat(25) + "^";
+        assert_eq!(
+            err.to_string(),
+            format!("Closed non-existant group\n\n{view}\n{marker}")
+        );
+    }
+
+    #[test]
+    fn negated_group() {
+        let pat: Pattern = "[^a-x]".parse().unwrap();
+        assert_eq!(pat.len(), 2);
+        assert!(pat.has("y"));
+        assert!(!pat.has("a"));
+
+        let pat: Pattern = "[^]".parse().unwrap();
+        assert_eq!(pat.len(), 26);
+    }
+
+    #[test]
+    fn classes() {
+        let pat: Pattern = "#@#".parse().unwrap();
+        assert_eq!(pat.len(), 21 * 5 * 21);
+        assert!(pat.has("cat"));
+        assert!(!pat.has("act"));
+
+        let pat: Pattern = "[@y][^#]".parse().unwrap();
+        assert_eq!(pat.len(), 6 * 5);
+        assert!(pat.has("ya"));
+
+        let classes = LetterClasses::new().with('%', "xyz");
+        let pat = Pattern::parse_with_classes("%[%a]", &Latin, &classes).unwrap();
+        assert_eq!(pat.len(), 3 * 4);
+        assert!(Pattern::parse_with_classes("@", &Latin, &classes).is_err());
+    }
+
+    #[test]
+    fn class_errors() {
+        let kind = |s: &str| s.parse::<Pattern>().unwrap_err().kind();
+        assert_eq!(kind("^a"), ErrorKind::Unexpected);
+        assert_eq!(kind("[a^]"), ErrorKind::Unexpected);
+        assert_eq!(kind("[a-@]"), ErrorKind::UnclosedRange);
+        assert_eq!(kind("[^a"), ErrorKind::UnclosedGroup);
+    }
 }
@@ -0,0 +1,190 @@
+use super::Pattern;
+use crate::{dawg::IndexDawg, ReadDawg, LATIN_CHARS};
+use std::collections::HashMap;
+
+mod node;
+use node::ShapeNode;
+
+/// A pattern where positions written with the same symbol must have the same<br>
+/// letter and positions written with different symbols must have different letters,<br>
+/// i.e. for solving cryptograms.
+///
+/// The symbols used don't matter, only where they repeat, so `ABCA`,<br>
+/// `1231` and `that` all describe the same shape. As the letters chosen<br>
+/// depend on earlier positions, each index tracks the letters bound so far,<br>
+/// so this is best combined with a DAWG, i.e. with [`And`](crate::And).
+///
+/// ```
+/// # use odawg::{from_word, into_word, And, FlatDawg, Pattern, ReadDawg, ShapePattern};
+/// let dawg: FlatDawg = ["that", "test", "tent", "teeth", "xyzzy", "fuzzy", "jazzy"]
+///     .map(from_word)
+///     .into_iter()
+///     .collect();
+///
+/// let shape = ShapePattern::new("ABCA");
+/// let words: Vec<_> = And(&dawg, &shape).words_sorted().map(into_word).collect();
+/// assert_eq!(words, ["tent", "test", "that"]);
+///
+/// // the shape of "fuzzy", which "xyzzy" doesn't have, with a vowel second
+/// let pattern: Pattern = "-@---".parse().unwrap();
+/// let shape = ShapePattern::new("fuzzy").with_pattern(&pattern);
+/// let words: Vec<_> = And(&dawg, &shape).words_sorted().map(into_word).collect();
+/// assert_eq!(words, ["fuzzy", "jazzy"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapePattern {
+    /// The variable at each position, numbered in order of first appearance
+    shape: Vec<usize>,
+    /// The letters allowed at each position
+    masks: Vec<u32>,
+}
+
+impl ShapePattern {
+    /// Creates a pattern from the shape of `symbols`,<br>
+    /// allowing any letter of the [`Latin`](crate::Latin) alphabet at each position.
+    pub fn new(symbols: impl AsRef<str>) -> Self {
+        let mut vars = HashMap::new();
+        let shape: Vec<_> = symbols
+            .as_ref()
+            .chars()
+            .map(|c| {
+                let next = vars.len();
+                *vars.entry(c).or_insert(next)
+            })
+            .collect();
+
+        Self {
+            masks: vec![Pattern::range_mask(0, LATIN_CHARS as u8 - 1); shape.len()],
+            shape,
+        }
+    }
+
+    /// Allows only the letters allowed by `pattern` at each position,<br>
+    /// so shapes can also be used with other alphabets.
+    ///
+    /// ## Panics
+    ///
+    /// If `pattern` has a different length to the shape.
+    pub fn with_pattern(mut self, pattern: &Pattern) -> Self {
+        assert_eq!(
+            self.masks.len(),
+            pattern.0.len(),
+            "Pattern has a different length to the shape"
+        );
+        self.masks.clone_from(&pattern.0);
+        self
+    }
+
+    /// The number of distinct letters in matching words
+    pub fn vars(&self) -> usize {
+        self.shape.iter().max().map_or(0, |&var| var + 1)
+    }
+}
+
+impl IndexDawg for ShapePattern {
+    type Idx = (usize, Vec<u8>);
+    const ROOT_IDX: Self::Idx = (0, Vec::new());
+
+    type NodeRef<'a> = ShapeNode<'a>;
+    fn index(&self, (pos, letters): Self::Idx) -> Self::NodeRef<'_> {
+        ShapeNode {
+            pattern: self,
+            pos,
+            letters,
+        }
+    }
+}
+
+impl ReadDawg for ShapePattern {
+    /// All the words matching this pattern, in ascending lexicographic order
+    fn words(&self) -> impl Iterator<Item = Vec<u8>> {
+        self.words_sorted()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ShapePattern;
+    use crate::{
+        dawg::IndexDawg, from_word, into_word, And, FlatDawg, Pattern, ReadDawg, ReadNode,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    /// Reference implementation, checking every pair of positions
+    fn matches(symbols: &str, masks: &[u32], word: &[u8]) -> bool {
+        let symbols: Vec<_> = symbols.chars().collect();
+        symbols.len() == word.len()
+            && word
+                .iter()
+                .zip(masks)
+                .all(|(&c, mask)| (mask >> c) & 1 == 1)
+            && (0..word.len()).all(|i| {
+                (0..word.len()).all(|j| (symbols[i] == symbols[j]) == (word[i] == word[j]))
+            })
+    }
+
+    proptest! {
+      #[test]
+      fn matches_reference(
+        symbols in "[a-d]{0,6}",
+        masks in vec(any::<u32>(), 6),
+        words in vec(vec(0..4u8, 0..7), 0..40),
+      ) {
+        let masks = &masks[..symbols.len()];
+        let shape = ShapePattern::new(&symbols).with_pattern(&Pattern(masks.to_vec()));
+        for word in &words {
+          assert_eq!(shape.has(word), matches(&symbols, masks, word), "{} {:?}", symbols, word);
+        }
+
+        let dawg: FlatDawg = words.iter().collect();
+        let expected: BTreeSet<_> = dawg.words().filter(|word| shape.has(word)).collect();
+        assert_eq!(And(&dawg, &shape).words().collect::<BTreeSet<_>>(), expected);
+      }
+
+      #[test]
+      fn next_c_matches(symbols in "[a-c]{1,4}", letters in vec(0..26u8, 0..4), c in 0..32u8) {
+        let shape = ShapePattern::new(&symbols);
+        let node = shape.index((0, letters));
+        let next = (c..32).find(|&c| node.has(c));
+        assert_eq!(node.next_c(c), next);
+      }
+    }
+
+    #[test]
+    fn distinct_letters() {
+        let shape = ShapePattern::new("abc");
+        assert_eq!(shape.vars(), 3);
+        assert_eq!(shape.len(), 26 * 25 * 24);
+        assert!(shape.has("cat"));
+        assert!(!shape.has("tat"));
+
+        let shape = ShapePattern::new("aa");
+        let words: Vec<_> = shape.words().take(3).map(into_word).collect();
+        assert_eq!(words, ["aa", "bb", "cc"]);
+    }
+
+    #[test]
+    fn empty() {
+        let shape = ShapePattern::new("");
+        assert_eq!(shape.vars(), 0);
+        assert_eq!(shape.words().collect::<Vec<_>>(), [from_word("")]);
+    }
+
+    #[test]
+    fn only_letters_allowed() {
+        let shape = ShapePattern::new("ab").with_pattern(&Pattern(vec![u32::MAX; 2]));
+        let node = shape.index(ShapePattern::ROOT_IDX);
+        assert!(node.has(30));
+        assert!(!node.has(31));
+        assert_eq!(node.next_c(31), None);
+        assert_eq!(shape.len(), 31 * 30);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pattern_length() {
+        ShapePattern::new("abc").with_pattern(&"ab".parse().unwrap());
+    }
+}
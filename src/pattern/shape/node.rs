@@ -0,0 +1,54 @@
+use super::ShapePattern;
+use crate::node::{ReadNode, CHILD_MASK};
+
+/// A node in a [`ShapePattern`], after `pos` letters have been matched,<br>
+/// with the `letters` bound to each variable seen so far.
+pub struct ShapeNode<'a> {
+    pub(super) pattern: &'a ShapePattern,
+    pub(super) pos: usize,
+    pub(super) letters: Vec<u8>,
+}
+
+impl ShapeNode<'_> {
+    /// Helper function for the mask of letters that can come next
+    fn allowed(&self) -> u32 {
+        let Some(&mask) = self.pattern.masks.get(self.pos) else {
+            return 0;
+        };
+        let mask = mask & CHILD_MASK;
+        let var = self.pattern.shape[self.pos];
+        match self.letters.get(var) {
+            Some(&c) => mask & (1 << c),
+            // unbound variables must differ from every bound letter
+            None => self.letters.iter().fold(mask, |mask, &c| mask & !(1 << c)),
+        }
+    }
+}
+
+impl ReadNode for ShapeNode<'_> {
+    type Idx = (usize, Vec<u8>);
+
+    fn is_empty(&self) -> bool {
+        self.allowed() == 0
+    }
+    fn is_end(&self) -> bool {
+        self.pos == self.pattern.shape.len()
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.allowed().checked_shr(c as u32).unwrap_or(0) & 1 == 1
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        let mut letters = self.letters.clone();
+        if self.pattern.shape.get(self.pos) == Some(&letters.len()) {
+            letters.push(c);
+        }
+        (self.pos + 1, letters)
+    }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        let masked = self.allowed() & u32::MAX.checked_shl(c as u32)?;
+        (masked > 0).then(|| masked.trailing_zeros() as u8)
+    }
+}
@@ -0,0 +1,194 @@
+use super::{node::Position, parse::Expr};
+
+/// The positions a part of an expression can start and end at
+struct Fragment {
+    first: Vec<usize>,
+    last: Vec<usize>,
+    nullable: bool,
+}
+
+impl Fragment {
+    /// The fragment for an expression that only matches the empty word
+    fn empty() -> Self {
+        Self {
+            first: vec![],
+            last: vec![],
+            nullable: true,
+        }
+    }
+}
+
+/// A position automaton, with a position for each letter mask in the<br>
+/// expression and the positions that can follow it.<br>
+/// Position 0 is the start, which matches no letters.
+struct Positions {
+    masks: Vec<u32>,
+    follow: Vec<Vec<usize>>,
+}
+
+impl Positions {
+    fn build(&mut self, expr: &Expr) -> Fragment {
+        match expr {
+            Expr::Mask(mask) => {
+                self.masks.push(*mask);
+                self.follow.push(vec![]);
+                let p = self.masks.len() - 1;
+                Fragment {
+                    first: vec![p],
+                    last: vec![p],
+                    nullable: false,
+                }
+            }
+            Expr::Concat(exprs) => exprs.iter().fold(Fragment::empty(), |acc, expr| {
+                let frag = self.build(expr);
+                self.then(acc, frag)
+            }),
+            Expr::Alt(exprs) => {
+                let mut acc = Fragment {
+                    nullable: false,
+                    ..Fragment::empty()
+                };
+                for expr in exprs {
+                    let frag = self.build(expr);
+                    acc.first.extend(frag.first);
+                    acc.last.extend(frag.last);
+                    acc.nullable |= frag.nullable;
+                }
+                acc
+            }
+            Expr::Repeat(expr, min, max) => {
+                // each repetition needs its own copy of the positions
+                let mut acc = Fragment::empty();
+                for _ in 0..*min {
+                    let frag = self.build(expr);
+                    acc = self.then(acc, frag);
+                }
+
+                let Some(max) = max else {
+                    let mut frag = self.build(expr);
+                    for &p in &frag.last {
+                        self.follow[p].extend(&frag.first);
+                    }
+                    frag.nullable = true;
+                    return self.then(acc, frag);
+                };
+                // optional copies are nested, i.e. `a{0,3}` as `(a(a(a)?)?)?`,
+                // so each copy is only followed by the next one
+                let frags: Vec<_> = (*min..*max).map(|_| self.build(expr)).collect();
+                let optional = frags
+                    .into_iter()
+                    .rev()
+                    .fold(Fragment::empty(), |opt, frag| {
+                        let mut frag = self.then(frag, opt);
+                        frag.nullable = true;
+                        frag
+                    });
+                self.then(acc, optional)
+            }
+        }
+    }
+
+    /// Helper function to match `frag0` followed by `frag1`
+    fn then(&mut self, frag0: Fragment, frag1: Fragment) -> Fragment {
+        for &p in &frag0.last {
+            self.follow[p].extend(&frag1.first);
+        }
+
+        let mut first = frag0.first;
+        if frag0.nullable {
+            first.extend(&frag1.first);
+        }
+        let mut last = frag1.last;
+        if frag1.nullable {
+            last.extend(frag0.last);
+        }
+        Fragment {
+            first,
+            last,
+            nullable: frag0.nullable && frag1.nullable,
+        }
+    }
+}
+
+/// Compiles an expression into its positions, with the start at 0.
+///
+/// The automaton's states are sets of these positions, which are only built<br>
+/// as they're traversed, as there can be exponentially many of them.<br>
+/// Positions that can't reach the end of a word are then removed,<br>
+/// so every state other than the root leads to a word.
+pub(super) fn compile(expr: &Expr) -> Vec<Position> {
+    let mut positions = Positions {
+        masks: vec![0],
+        follow: vec![vec![]],
+    };
+    let root = positions.build(expr);
+    positions.follow[0] = root.first;
+    let mut ends = vec![false; positions.masks.len()];
+    for p in root.last {
+        ends[p] = true;
+    }
+    ends[0] = root.nullable;
+
+    let positions = positions
+        .masks
+        .into_iter()
+        .zip(positions.follow)
+        .zip(ends)
+        .map(|((mask, follow), end)| Position { mask, end, follow })
+        .collect();
+    trim(positions)
+}
+
+/// Helper function to remove all positions that can't be reached<br>
+/// from the start or can't reach the end of a word.
+pub(super) fn trim(positions: Vec<Position>) -> Vec<Position> {
+    // positions without letters can never be reached
+    let mut reached = vec![false; positions.len()];
+    reached[0] = true;
+    let mut stack = vec![0];
+    while let Some(p) = stack.pop() {
+        for &q in &positions[p].follow {
+            if positions[q].mask != 0 && !std::mem::replace(&mut reached[q], true) {
+                stack.push(q);
+            }
+        }
+    }
+
+    let mut parents = vec![vec![]; positions.len()];
+    for (p, pos) in positions.iter().enumerate() {
+        for &q in &pos.follow {
+            parents[q].push(p);
+        }
+    }
+    let mut live: Vec<bool> = positions.iter().map(|pos| pos.end).collect();
+    let mut stack: Vec<usize> = (0..positions.len()).filter(|&p| live[p]).collect();
+    while let Some(q) = stack.pop() {
+        for &p in &parents[q] {
+            if !std::mem::replace(&mut live[p], true) {
+                stack.push(p);
+            }
+        }
+    }
+
+    // kept positions keep their order, so the start stays at 0
+    let mut count = 0;
+    let new_idxs: Vec<Option<usize>> = (0..positions.len())
+        .map(|p| {
+            let keep = p == 0 || (reached[p] && live[p]);
+            count += usize::from(keep);
+            keep.then(|| count - 1)
+        })
+        .collect();
+
+    positions
+        .into_iter()
+        .zip(&new_idxs)
+        .filter(|(_, new_idx)| new_idx.is_some())
+        .map(|(mut pos, _)| {
+            pos.follow = pos.follow.iter().filter_map(|&q| new_idxs[q]).collect();
+            pos.follow.sort_unstable();
+            pos.follow.dedup();
+            pos
+        })
+        .collect()
+}
@@ -0,0 +1,435 @@
+use super::{LetterClasses, ParseError};
+use crate::{
+    dawg::{product, Finished, IndexDawg},
+    node::CHILD_MASK,
+    utils::{Alphabet, Latin},
+    MapError, ReadDawg, THIN_CHARS,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+mod compile;
+mod node;
+use node::Position;
+pub use node::VarNode;
+mod parse;
+
+/// A pattern that can match words of different lengths,<br>
+/// which can be traversed like a DAWG.
+///
+/// This extends the syntax of [`Pattern`](crate::Pattern) with:
+/// - `*`, matching any run of letters, including none
+/// - `?` after an item, i.e. `colou?r`, making it optional
+/// - `{m,n}` after an item, repeating it from `m` to `n` times.<br>
+///   Either bound can be left out, i.e. `{m,}` or `{,n}`, and `{m}` repeats exactly `m` times.<br>
+///   Bounds can be at most 100 and, as each repetition is compiled separately,<br>
+///   repetitions can't expand the pattern to more than 1000 letters in total.
+/// - `(a|b)`, matching any of the options separated by `|`
+///
+/// Each index is the set of letters in the pattern that the last letter<br>
+/// could have matched, so only the states that are actually reached are built.
+///
+/// Patterns using `*` or `{m,}` match infinitely many words,<br>
+/// so they should be combined with a DAWG, i.e. with [`And`](crate::And),<br>
+/// rather than having their own words iterated over.
+///
+/// ```
+/// # use odawg::{from_word, into_word, And, FlatDawg, ReadDawg, VarPattern};
+/// let dawg: FlatDawg = ["rebuilding", "reading", "red", "ring", "quiet", "quill", "queue"]
+///     .map(from_word)
+///     .into_iter()
+///     .collect();
+///
+/// let pattern: VarPattern = "re*ing".parse().unwrap();
+/// let words: Vec<_> = And(&dawg, &pattern).words_sorted().map(into_word).collect();
+/// assert_eq!(words, ["reading", "rebuilding"]);
+///
+/// // 5 letter words with a `q` in them
+/// let pattern: VarPattern = "(q-{4}|-q-{3}|-{2}q-{2}|-{3}q-|-{4}q)".parse().unwrap();
+/// assert_eq!(And(&dawg, &pattern).len(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Position>")]
+pub struct VarPattern(Vec<Position>);
+
+impl TryFrom<Vec<Position>> for VarPattern {
+    type Error = MapError;
+    /// Checks the positions of a deserialized pattern, removing<br>
+    /// any that can't reach the end of a word, as parsing does.
+    fn try_from(positions: Vec<Position>) -> Result<Self, Self::Error> {
+        if positions.is_empty() {
+            return Err(MapError::Empty);
+        }
+        // the start matches no letters, so can't follow any position
+        if positions[0].mask != 0 {
+            return Err(MapError::Mask(0));
+        }
+        for (p, pos) in positions.iter().enumerate() {
+            if pos.mask & !CHILD_MASK != 0 {
+                return Err(MapError::Mask(p));
+            }
+            if pos.follow.iter().any(|&q| q >= positions.len()) {
+                return Err(MapError::Bounds(p));
+            }
+        }
+        Ok(Self(compile::trim(positions)))
+    }
+}
+
+impl VarPattern {
+    /// Parses a pattern whose letters are written with `alphabet`,<br>
+    /// as with [`Pattern::parse_with`](crate::Pattern::parse_with).
+    ///
+    /// ## Panics
+    ///
+    /// If `alphabet` has more than [`THIN_CHARS`](crate::THIN_CHARS) letters.
+    pub fn parse_with(s: &str, alphabet: &impl Alphabet) -> Result<Self, ParseError> {
+        Self::parse_with_classes(s, alphabet, &LetterClasses::vowels(alphabet))
+    }
+
+    /// Parses a pattern whose letters are written with `alphabet`,<br>
+    /// as with [`Pattern::parse_with_classes`](crate::Pattern::parse_with_classes).
+    ///
+    /// ## Panics
+    ///
+    /// If `alphabet` has more than [`THIN_CHARS`](crate::THIN_CHARS) letters.
+    pub fn parse_with_classes(
+        s: &str,
+        alphabet: &impl Alphabet,
+        classes: &LetterClasses,
+    ) -> Result<Self, ParseError> {
+        assert!(
+            alphabet.len() <= THIN_CHARS,
+            "Patterns support at most {THIN_CHARS} letters"
+        );
+        let specials = classes.specials(parse::SPECIALS);
+        let expr = parse::Parser::new(s, alphabet, classes, &specials).parse()?;
+        Ok(Self(compile::compile(&expr)))
+    }
+
+    /// The number of letters in the pattern, after repetitions are expanded,<br>
+    /// including the start, which matches no letters
+    pub fn node_count(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the pattern only matches finitely many words,<br>
+    /// which is when no letter in the pattern can be followed by itself.
+    pub fn is_finite(&self) -> bool {
+        // letters are removed once nothing is left before them
+        let mut parents = vec![0usize; self.0.len()];
+        for pos in &self.0 {
+            for &q in &pos.follow {
+                parents[q] += 1;
+            }
+        }
+        let mut stack: Vec<usize> = (0..self.0.len()).filter(|&p| parents[p] == 0).collect();
+        let mut removed = 0;
+        while let Some(p) = stack.pop() {
+            removed += 1;
+            for &q in &self.0[p].follow {
+                parents[q] -= 1;
+                if parents[q] == 0 {
+                    stack.push(q);
+                }
+            }
+        }
+        removed == self.0.len()
+    }
+}
+
+impl FromStr for VarPattern {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Latin)
+    }
+}
+
+impl IndexDawg for VarPattern {
+    type Idx = Vec<usize>;
+    const ROOT_IDX: Self::Idx = Vec::new();
+
+    type NodeRef<'a> = VarNode<'a>;
+    fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        VarNode::new(&self.0, &idx)
+    }
+}
+
+impl ReadDawg for VarPattern {
+    /// All the words matching this pattern, in ascending lexicographic order.
+    ///
+    /// This only terminates if the pattern [`is_finite`](VarPattern::is_finite).
+    fn words(&self) -> impl Iterator<Item = Vec<u8>> {
+        self.words_sorted()
+    }
+
+    /// The number of words matching this pattern,<br>
+    /// or `usize::MAX` if it matches infinitely many or too many to count.
+    ///
+    /// Each state reached is only counted once, but patterns can have<br>
+    /// exponentially many states, i.e. `-{,20}a-{20}`.
+    fn len(&self) -> usize {
+        if !self.is_finite() {
+            return usize::MAX;
+        }
+        let count = |(end, children): Finished<usize>| {
+            children
+                .iter()
+                .fold(usize::from(end), |count, &(_, n)| count.saturating_add(n))
+        };
+        let (root, _) = product(self, |node| Some(count(node)));
+        count(root)
+    }
+
+    /// As every letter in the pattern can reach the end of a word, this only<br>
+    /// needs to check whether the root is the end of a word or has children.
+    fn is_empty(&self) -> bool {
+        let root = &self.0[0];
+        !root.end && root.follow.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        parse::{Expr, Parser},
+        Position, VarPattern,
+    };
+    use crate::{
+        dawg::IndexDawg, from_word, into_word, node::ReadNode, pattern::strategies::pattern_len,
+        Alphabet, And, ErrorKind, FlatDawg, Latin, LetterClasses, MapError, ReadDawg, Tiles,
+    };
+    use prop::collection::vec;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    /// Generates a pattern over the letters `a-c`, written out as a string
+    fn pattern_str() -> BoxedStrategy<String> {
+        let leaf = prop_oneof!["[abc]", Just("-".to_string()), Just("*".to_string())];
+        let repeat = prop::sample::select(vec!["?", "{2}", "{1,2}", "{,2}", "{1,}"]);
+        leaf.prop_recursive(4, 16, 4, move |inner| {
+            prop_oneof![
+                vec(inner.clone(), 0..4).prop_map(|items| items.concat()),
+                vec(inner.clone(), 1..4).prop_map(|items| format!("({})", items.join("|"))),
+                (inner, repeat.clone()).prop_map(|(item, repeat)| format!("({item}){repeat}")),
+            ]
+        })
+        .boxed()
+    }
+
+    /// Reference implementation, finding every index matching `expr` could end at
+    fn ends(expr: &Expr, word: &[u8], start: usize) -> BTreeSet<usize> {
+        match expr {
+            Expr::Mask(mask) => word
+                .get(start)
+                .filter(|&&c| (mask >> c) & 1 == 1)
+                .map(|_| start + 1)
+                .into_iter()
+                .collect(),
+            Expr::Concat(exprs) => exprs.iter().fold(BTreeSet::from([start]), |acc, expr| {
+                acc.into_iter().flat_map(|i| ends(expr, word, i)).collect()
+            }),
+            Expr::Alt(exprs) => exprs
+                .iter()
+                .flat_map(|expr| ends(expr, word, start))
+                .collect(),
+            Expr::Repeat(expr, min, max) => {
+                let mut found = BTreeSet::new();
+                let mut acc = BTreeSet::from([start]);
+                for i in 0..=max.unwrap_or(word.len() + *min) {
+                    if i >= *min {
+                        found.extend(&acc);
+                    }
+                    acc = acc.into_iter().flat_map(|j| ends(expr, word, j)).collect();
+                }
+                found
+            }
+        }
+    }
+
+    proptest! {
+      #[test]
+      fn matches_reference(s in pattern_str(), words in vec(vec(0..3u8, 0..6), 0..20)) {
+        let specials = super::parse::SPECIALS;
+        let expr = Parser::new(&s, &Latin, &LetterClasses::new(), specials).parse().unwrap();
+        let pattern: VarPattern = s.parse().unwrap();
+        for word in &words {
+          let expected = ends(&expr, word, 0).contains(&word.len());
+          assert_eq!(pattern.has(word), expected, "{} {:?}", s, word);
+        }
+
+        let dawg: FlatDawg = words.iter().collect();
+        let expected: BTreeSet<_> = dawg.words().filter(|word| pattern.has(word)).collect();
+        assert_eq!(And(&dawg, &pattern).words().collect::<BTreeSet<_>>(), expected);
+      }
+
+      #[test]
+      fn extends_pattern(pattern in pattern_len(1000)) {
+        prop_assume!(!pattern.0.is_empty());
+        let var: VarPattern = pattern.to_string().parse().unwrap();
+        assert_eq!(var.words().collect::<Vec<_>>(), pattern.words().collect::<Vec<_>>());
+        assert!(var.is_finite());
+      }
+    }
+
+    #[test]
+    fn repetition() {
+        let pattern: VarPattern = "-{2,3}".parse().unwrap();
+        assert_eq!(pattern.len(), 26 * 26 + 26 * 26 * 26);
+        assert!(!pattern.has("a"));
+        assert!(pattern.has("aaa"));
+
+        let pattern: VarPattern = "colou?r".parse().unwrap();
+        let words: Vec<_> = pattern.words().map(into_word).collect();
+        assert_eq!(words, ["color", "colour"]);
+
+        let pattern: VarPattern = "(ab){2,}".parse().unwrap();
+        assert!(!pattern.is_finite());
+        assert_eq!(pattern.len(), usize::MAX);
+        assert!(pattern.has("ababab"));
+        assert!(!pattern.has("aba"));
+
+        let pattern: VarPattern = "-{14}".parse().unwrap();
+        assert!(pattern.is_finite());
+        assert_eq!(pattern.len(), usize::MAX);
+
+        let pattern: VarPattern = "a{,2}".parse().unwrap();
+        let words: Vec<_> = pattern.words().map(into_word).collect();
+        assert_eq!(words, ["", "a", "aa"]);
+    }
+
+    #[test]
+    fn alternation() {
+        let pattern: VarPattern = "(cat|dog)s?".parse().unwrap();
+        let words: Vec<_> = pattern.words().map(into_word).collect();
+        assert_eq!(words, ["cat", "cats", "dog", "dogs"]);
+
+        let pattern: VarPattern = "(|a)".parse().unwrap();
+        assert_eq!(pattern.len(), 2);
+        let pattern: VarPattern = "".parse().unwrap();
+        assert_eq!(pattern.words().collect::<Vec<_>>(), [vec![]]);
+    }
+
+    #[test]
+    fn empty() {
+        let pattern: VarPattern = "[]*".parse().unwrap();
+        assert!(pattern.is_empty());
+        assert!(pattern.is_finite());
+        assert_eq!(pattern.node_count(), 1);
+        assert!(!pattern.has(""));
+    }
+
+    #[test]
+    fn many_states() {
+        // every subset of the last 31 letters is a separate state
+        let pattern: VarPattern = "*a-{30}".parse().unwrap();
+        assert_eq!(pattern.node_count(), 33);
+        assert!(!pattern.is_finite());
+
+        let word = format!("xya{}", "b".repeat(30));
+        assert!(pattern.has(&word));
+        assert!(!pattern.has(&word[..word.len() - 1]));
+        let dawg: FlatDawg = [word.as_str(), "abc"].map(from_word).into_iter().collect();
+        assert_eq!(
+            And(&dawg, &pattern)
+                .words()
+                .map(into_word)
+                .collect::<Vec<_>>(),
+            [word]
+        );
+    }
+
+    #[test]
+    fn both_patterns() {
+        let dawg: FlatDawg = ["quiet", "quill", "queue", "squid", "quilt"]
+            .map(from_word)
+            .into_iter()
+            .collect();
+        let pattern0: VarPattern = "*qu*".parse().unwrap();
+        let pattern1: VarPattern = "-*t".parse().unwrap();
+        let words: Vec<_> = And(&dawg, And(&pattern0, &pattern1))
+            .words_sorted()
+            .map(into_word)
+            .collect();
+        assert_eq!(words, ["quiet", "quilt"]);
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let pattern: VarPattern = "(cat|dog)s?*".parse().unwrap();
+        let bytes = bincode::serialize(&pattern).unwrap();
+        assert_eq!(bincode::deserialize::<VarPattern>(&bytes).unwrap(), pattern);
+    }
+
+    #[test]
+    fn invalid_positions() {
+        let pos = |mask, end, follow: &[usize]| Position {
+            mask,
+            end,
+            follow: follow.to_vec(),
+        };
+        assert_eq!(VarPattern::try_from(vec![]), Err(MapError::Empty));
+        let start = VarPattern::try_from(vec![pos(1, true, &[])]);
+        assert_eq!(start, Err(MapError::Mask(0)));
+        let mask = VarPattern::try_from(vec![pos(0, false, &[1]), pos(1 << 31, true, &[])]);
+        assert_eq!(mask, Err(MapError::Mask(1)));
+        let bounds = VarPattern::try_from(vec![pos(0, false, &[1]), pos(1, true, &[2])]);
+        assert_eq!(bounds, Err(MapError::Bounds(1)));
+
+        // `b` can't reach the end of a word, so is removed
+        let pattern = VarPattern::try_from(vec![
+            pos(0, false, &[1, 2]),
+            pos(0b01, true, &[]),
+            pos(0b10, false, &[]),
+        ])
+        .unwrap();
+        assert_eq!(pattern, "a".parse().unwrap());
+        assert_eq!(pattern.index(VarPattern::ROOT_IDX).next_c(0), Some(0));
+        assert_eq!(pattern.index(VarPattern::ROOT_IDX).next_c(1), None);
+    }
+
+    #[test]
+    fn tiles() {
+        let alphabet = Tiles::new(["a", "b", "c", "ch", "h"]);
+        let pattern = VarPattern::parse_with("(ch|c)a*", &alphabet).unwrap();
+        assert!(pattern.has(alphabet.spell("chab")));
+        assert!(pattern.has(alphabet.spell("ca")));
+        assert!(!pattern.has(alphabet.spell("ha")));
+    }
+
+    #[test]
+    fn errors() {
+        let kind = |s: &str| s.parse::<VarPattern>().unwrap_err().kind();
+        assert_eq!(kind("(ab"), ErrorKind::UnclosedBracket);
+        assert_eq!(kind("ab)"), ErrorKind::ReclosedBracket);
+        assert_eq!(kind("?a"), ErrorKind::NothingToRepeat);
+        assert_eq!(kind("(|{2})"), ErrorKind::NothingToRepeat);
+        assert_eq!(kind("a{3,2}"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("a{}"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("a{2"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("a{x}"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("a]"), ErrorKind::ReclosedGroup);
+        assert_eq!(kind("a,"), ErrorKind::Unexpected);
+        assert_eq!(kind("a{101}"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("a{100000000}"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("a{2,101}"), ErrorKind::InvalidRepeat);
+        assert_eq!(kind("(a{100}){11}"), ErrorKind::InvalidRepeat);
+        assert_eq!(
+            kind("(-{100}){9}b*(c{,10}|d){,10}"),
+            ErrorKind::InvalidRepeat
+        );
+        assert!("(-{100}){9}b*(c{,10}|d){,8}".parse::<VarPattern>().is_ok());
+
+        let err = "a{3,2}b".parse::<VarPattern>().unwrap_err();
+        assert_eq!(err.span(), 5..6);
+        let err = "(a[t-r])".parse::<VarPattern>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ReversedRange);
+        assert_eq!(err.span(), 3..6);
+        let err = "(a|b".parse::<VarPattern>().unwrap_err();
+        assert_eq!(err.span(), 4..4);
+        let err = "a{1000}".parse::<VarPattern>().unwrap_err();
+        assert_eq!(err.span(), 5..6);
+        let err = "(a{100}){11}".parse::<VarPattern>().unwrap_err();
+        assert_eq!(err.span(), 8..9);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::node::ReadNode;
+use serde::{Deserialize, Serialize};
+
+/// A position in a [`VarPattern`](super::VarPattern)'s expression,<br>
+/// with the letters it matches and the positions that can follow it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct Position {
+    pub(super) mask: u32,
+    pub(super) end: bool,
+    pub(super) follow: Vec<usize>,
+}
+
+/// A state of a [`VarPattern`](super::VarPattern)'s automaton,<br>
+/// from the set of positions that could have matched the last letter.
+pub struct VarNode<'a> {
+    positions: &'a [Position],
+    /// The positions that could match the next letter, sorted
+    next: Vec<usize>,
+    /// The letters matched by any of `next`
+    letters: u32,
+    end: bool,
+}
+
+impl<'a> VarNode<'a> {
+    /// Builds the state for the positions in `set`,<br>
+    /// where the empty set is the start position.
+    pub(super) fn new(positions: &'a [Position], set: &[usize]) -> Self {
+        let set = if set.is_empty() { &[0][..] } else { set };
+        let mut next: Vec<usize> = set
+            .iter()
+            .flat_map(|&p| &positions[p].follow)
+            .copied()
+            .collect();
+        next.sort_unstable();
+        next.dedup();
+
+        Self {
+            positions,
+            letters: next.iter().fold(0, |acc, &q| acc | positions[q].mask),
+            next,
+            end: set.iter().any(|&p| positions[p].end),
+        }
+    }
+}
+
+impl ReadNode for VarNode<'_> {
+    type Idx = Vec<usize>;
+
+    fn is_empty(&self) -> bool {
+        self.letters == 0
+    }
+    fn is_end(&self) -> bool {
+        self.end
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.letters.checked_shr(c as u32).unwrap_or(0) & 1 == 1
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        self.next
+            .iter()
+            .copied()
+            .filter(|&q| self.positions[q].mask.checked_shr(c as u32).unwrap_or(0) & 1 == 1)
+            .collect()
+    }
+
+    fn next_c(&self, c: u8) -> Option<u8> {
+        let masked = self.letters & u32::MAX.checked_shl(c as u32)?;
+        (masked > 0).then(|| masked.trailing_zeros() as u8)
+    }
+}
@@ -0,0 +1,239 @@
+use super::super::{
+    parse::{Token, Tokens},
+    ErrorKind, LetterClasses, ParseError, Pattern,
+};
+use crate::utils::Alphabet;
+use std::iter::Peekable;
+
+/// A parsed [`VarPattern`](super::VarPattern), before it's compiled
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Expr {
+    /// A single letter, from a mask of letters
+    Mask(u32),
+    /// Each of the expressions, one after the other
+    Concat(Vec<Expr>),
+    /// Any one of the expressions
+    Alt(Vec<Expr>),
+    /// The expression repeated at least `min` times<br>
+    /// and at most `max` times, if `max` is given
+    Repeat(Box<Expr>, usize, Option<usize>),
+}
+
+impl Expr {
+    /// The number of letter positions the expression compiles to,<br>
+    /// as each repetition needs its own copy of the positions.
+    fn positions(&self) -> usize {
+        match self {
+            Self::Mask(_) => 1,
+            Self::Concat(exprs) | Self::Alt(exprs) => exprs.iter().map(Self::positions).sum(),
+            Self::Repeat(expr, min, max) => {
+                // unbounded repetitions need one more copy to loop on
+                let copies = max.unwrap_or(min + 1);
+                expr.positions().saturating_mul(copies)
+            }
+        }
+    }
+}
+
+/// The characters with special meaning in a [`VarPattern`](super::VarPattern),<br>
+/// alongside the names of any classes
+pub(super) const SPECIALS: &str = "-[]^*?{},()|";
+
+/// The largest bound allowed in a repetition
+pub(super) const MAX_REPEAT: usize = 100;
+
+/// The most letter positions repetitions can expand a pattern to
+pub(super) const MAX_POSITIONS: usize = 1000;
+
+/// A recursive descent parser, following the grammar:
+///
+/// ```text
+/// alt     := concat ('|' concat)*
+/// concat  := (atom postfix*)*
+/// atom    := letter | class | '-' | '*' | '[' group | '(' alt ')'
+/// postfix := '?' | '{' number? (',' number?)? '}'
+/// ```
+pub(super) struct Parser<'a, A: Alphabet> {
+    input: &'a str,
+    last: u8,
+    classes: &'a LetterClasses,
+    tokens: Peekable<Tokens<'a, A>>,
+    /// The number of letter positions parsed so far
+    positions: usize,
+}
+
+impl<'a, A: Alphabet> Parser<'a, A> {
+    /// Creates a parser for `input`, where `specials` should be<br>
+    /// [`SPECIALS`] followed by the names of the `classes`.
+    pub(super) fn new(
+        input: &'a str,
+        alphabet: &'a A,
+        classes: &'a LetterClasses,
+        specials: &'a str,
+    ) -> Self {
+        Self {
+            input,
+            last: alphabet.len().saturating_sub(1) as u8,
+            classes,
+            tokens: Tokens::new(alphabet, input, specials).peekable(),
+            positions: 0,
+        }
+    }
+
+    /// Helper function for an error at the character starting at byte `idx`
+    fn error(&self, idx: usize, kind: ErrorKind) -> ParseError {
+        ParseError::new(self.input, idx, kind)
+    }
+
+    /// Parses the whole input as a single expression
+    pub(super) fn parse(mut self) -> Result<Expr, ParseError> {
+        let expr = self.alt()?;
+        match self.tokens.next() {
+            Some((idx, _)) => Err(self.error(idx, ErrorKind::ReclosedBracket)),
+            None => Ok(expr),
+        }
+    }
+
+    fn alt(&mut self) -> Result<Expr, ParseError> {
+        let mut options = vec![self.concat()?];
+        while self
+            .tokens
+            .next_if(|&(_, t)| t == Token::Char('|'))
+            .is_some()
+        {
+            options.push(self.concat()?);
+        }
+
+        if options.len() == 1 {
+            return Ok(options.swap_remove(0));
+        }
+        Ok(Expr::Alt(options))
+    }
+
+    fn concat(&mut self) -> Result<Expr, ParseError> {
+        let mut exprs = vec![];
+        while let Some(&(idx, token)) = self.tokens.peek() {
+            if matches!(token, Token::Char('|' | ')')) {
+                break;
+            }
+            self.tokens.next();
+            let atom = self.atom(idx, token)?;
+            exprs.push(self.postfix(atom)?);
+        }
+
+        if exprs.len() == 1 {
+            return Ok(exprs.swap_remove(0));
+        }
+        Ok(Expr::Concat(exprs))
+    }
+
+    fn atom(&mut self, idx: usize, token: Token) -> Result<Expr, ParseError> {
+        let any = Pattern::range_mask(0, self.last);
+        match token {
+            Token::Letter(v) => {
+                self.positions += 1;
+                Ok(Expr::Mask(Pattern::range_mask(v, v)))
+            }
+            Token::Char('-') => {
+                self.positions += 1;
+                Ok(Expr::Mask(any))
+            }
+            Token::Char('*') => {
+                self.positions += 1;
+                Ok(Expr::Repeat(Box::new(Expr::Mask(any)), 0, None))
+            }
+            Token::Char('[') => {
+                self.positions += 1;
+                let mask =
+                    Pattern::parse_group(self.input, self.last, self.classes, &mut self.tokens)?;
+                Ok(Expr::Mask(mask))
+            }
+            Token::Char('(') => {
+                let expr = self.alt()?;
+                match self.tokens.next() {
+                    Some(_) => Ok(expr),
+                    None => Err(self.error(self.input.len(), ErrorKind::UnclosedBracket)),
+                }
+            }
+
+            // error state handling
+            Token::Char(']') => Err(self.error(idx, ErrorKind::ReclosedGroup)),
+            Token::Char('?' | '{') => Err(self.error(idx, ErrorKind::NothingToRepeat)),
+            Token::Char(c) => match self.classes.get(c) {
+                Some(class) => {
+                    self.positions += 1;
+                    Ok(Expr::Mask(class))
+                }
+                None => Err(self.error(idx, ErrorKind::Unexpected)),
+            },
+        }
+    }
+
+    fn postfix(&mut self, mut expr: Expr) -> Result<Expr, ParseError> {
+        loop {
+            let (idx, (min, max)) = match self.tokens.peek() {
+                Some(&(idx, Token::Char('?'))) => {
+                    self.tokens.next();
+                    (idx, (0, Some(1)))
+                }
+                Some(&(idx, Token::Char('{'))) => {
+                    self.tokens.next();
+                    (idx, self.repeat()?)
+                }
+                _ => return Ok(expr),
+            };
+
+            // replace the positions of `expr` with those of every copy of it
+            let before = expr.positions();
+            expr = Expr::Repeat(Box::new(expr), min, max);
+            self.positions = (self.positions - before).saturating_add(expr.positions());
+            if self.positions > MAX_POSITIONS {
+                return Err(self.error(idx, ErrorKind::InvalidRepeat));
+            }
+        }
+    }
+
+    /// Parses the bounds of a repetition, after its opening `{`
+    fn repeat(&mut self) -> Result<(usize, Option<usize>), ParseError> {
+        let min = self.number()?;
+        let max = match self.tokens.next() {
+            Some((_, Token::Char(','))) => self.number()?,
+            Some((idx, Token::Char('}'))) => {
+                let min = min.ok_or_else(|| self.error(idx, ErrorKind::InvalidRepeat))?;
+                return Ok((min, Some(min)));
+            }
+            Some((idx, _)) => return Err(self.error(idx, ErrorKind::InvalidRepeat)),
+            None => return Err(self.error(self.input.len(), ErrorKind::InvalidRepeat)),
+        };
+
+        match self.tokens.next() {
+            Some((idx, Token::Char('}'))) => {
+                let min = min.unwrap_or(0);
+                if max.is_some_and(|max| max < min) {
+                    return Err(self.error(idx, ErrorKind::InvalidRepeat));
+                }
+                Ok((min, max))
+            }
+            Some((idx, _)) => Err(self.error(idx, ErrorKind::InvalidRepeat)),
+            None => Err(self.error(self.input.len(), ErrorKind::InvalidRepeat)),
+        }
+    }
+
+    /// Parses a run of decimal digits, if there are any,<br>
+    /// failing if the number is larger than [`MAX_REPEAT`]
+    fn number(&mut self) -> Result<Option<usize>, ParseError> {
+        let mut number: Option<usize> = None;
+        while let Some(&(idx, Token::Char(c))) = self.tokens.peek() {
+            let Some(digit) = c.to_digit(10) else {
+                break;
+            };
+            self.tokens.next();
+            let n = number.unwrap_or(0) * 10 + digit as usize;
+            if n > MAX_REPEAT {
+                return Err(self.error(idx, ErrorKind::InvalidRepeat));
+            }
+            number = Some(n);
+        }
+        Ok(number)
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    dawg::IndexDawg,
+    utils::convert::{try_from_alpha, IntoLetters, LetterError},
+};
+use std::str::FromStr;
+
+mod node;
+use node::RackNode;
+
+/// A multiset of letter tiles, alongside a number of blank tiles<br>
+/// that can be used in place of any letter.
+///
+/// Racks are parsed from the [`Latin`](crate::Latin) alphabet, with `?` for blanks.
+///
+/// ```
+/// # use odawg::{from_word, into_word, FlatDawg, Rack, ReadDawg};
+/// let dawg: FlatDawg = ["ate", "eat", "tea", "teas", "at", "seat"].map(from_word).into_iter().collect();
+///
+/// let rack: Rack = "tea".parse().unwrap();
+/// let mut words: Vec<_> = dawg.anagrams(&rack).map(into_word).collect();
+/// words.sort();
+/// assert_eq!(words, ["ate", "eat", "tea"]);
+///
+/// let rack: Rack = "ta?".parse().unwrap();
+/// let mut words: Vec<_> = dawg.sub_anagrams(&rack).map(into_word).collect();
+/// words.sort();
+/// assert_eq!(words, ["at", "ate", "eat", "tea"]);
+///
+/// // the blank stands in for the 'e' in "tea"
+/// assert_eq!(rack.blanks_in("tea"), Some(vec![1]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rack {
+    /// The number of tiles for each letter
+    counts: Vec<usize>,
+    blanks: usize,
+}
+
+impl Rack {
+    /// Creates a rack from its letter tiles and number of blanks
+    pub fn new(letters: impl IntoLetters, blanks: usize) -> Self {
+        let mut rack = Self {
+            counts: vec![],
+            blanks,
+        };
+        for c in letters.letters() {
+            rack.add(c);
+        }
+        rack
+    }
+
+    /// The total number of tiles, including blanks
+    pub fn len(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.blanks
+    }
+
+    /// Whether there are no tiles in the rack
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of tiles for the letter `c`, not including blanks
+    pub fn count(&self, c: u8) -> usize {
+        self.counts.get(c as usize).copied().unwrap_or(0)
+    }
+
+    /// The number of blank tiles
+    pub fn blanks(&self) -> usize {
+        self.blanks
+    }
+
+    /// The positions in `word` that need blanks when spelling<br>
+    /// it with this rack, or `None` if it can't be spelled.
+    ///
+    /// Letter tiles are always used before blanks, so blanks<br>
+    /// are used for the last occurrences of each letter.
+    pub fn blanks_in(&self, word: impl IntoLetters) -> Option<Vec<usize>> {
+        let mut used = Rack::default();
+        let mut blanks = vec![];
+        for (i, c) in word.letters().enumerate() {
+            if !used.take(self, c) {
+                return None;
+            }
+            if used.blanks > blanks.len() {
+                blanks.push(i);
+            }
+        }
+        Some(blanks)
+    }
+
+    /// Helper function to add a tile for the letter `c`
+    fn add(&mut self, c: u8) {
+        let c = c as usize;
+        if self.counts.len() <= c {
+            self.counts.resize(c + 1, 0);
+        }
+        self.counts[c] += 1;
+    }
+
+    /// Helper function to use a tile from `rack` for the letter `c`,<br>
+    /// where `self` is the tiles that have been used so far.
+    ///
+    /// Returns whether there was a tile left to use.
+    fn take(&mut self, rack: &Rack, c: u8) -> bool {
+        if self.count(c) < rack.count(c) {
+            self.add(c);
+        } else if self.blanks < rack.blanks {
+            self.blanks += 1;
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+impl FromStr for Rack {
+    type Err = LetterError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rack = Rack::default();
+        for (idx, char) in s.char_indices() {
+            match char {
+                '?' => rack.blanks += 1,
+                _ => rack.add(try_from_alpha(char).ok_or(LetterError { char, idx })?),
+            }
+        }
+        Ok(rack)
+    }
+}
+
+/// A view of a [`Rack`] as a DAWG of all the words it can spell,<br>
+/// indexed by the tiles used so far.
+pub struct RackDawg<'a> {
+    rack: &'a Rack,
+    /// Whether words need to use every tile in the rack
+    whole: bool,
+}
+
+impl<'a> RackDawg<'a> {
+    /// A DAWG of the words that use all of the tiles in `rack`
+    pub fn anagrams(rack: &'a Rack) -> Self {
+        Self { rack, whole: true }
+    }
+
+    /// A DAWG of the words that use any of the tiles in `rack`
+    pub fn sub_anagrams(rack: &'a Rack) -> Self {
+        Self { rack, whole: false }
+    }
+}
+
+impl IndexDawg for RackDawg<'_> {
+    type Idx = Rack;
+    const ROOT_IDX: Self::Idx = Rack {
+        counts: vec![],
+        blanks: 0,
+    };
+
+    type NodeRef<'b>
+        = RackNode<'b>
+    where
+        Self: 'b;
+    fn index(&self, idx: Self::Idx) -> Self::NodeRef<'_> {
+        RackNode {
+            rack: self.rack,
+            used: idx,
+            whole: self.whole,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rack;
+    use crate::{FlatDawg, LetterError, ReadDawg};
+    use prop::collection::vec;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn rack() -> BoxedStrategy<Rack> {
+        (vec(0..4u8, 0..6), 0..3usize)
+            .prop_map(|(letters, blanks)| Rack::new(letters, blanks))
+            .boxed()
+    }
+
+    proptest! {
+      #[test]
+      fn sub_anagrams_can_be_spelled(words in vec(vec(0..4u8, 0..8), 0..50), rack in rack()) {
+        let dawg: FlatDawg = words.iter().collect();
+        let found: BTreeSet<_> = dawg.sub_anagrams(&rack).collect();
+        let expected: BTreeSet<_> = dawg
+          .words()
+          .filter(|word| rack.blanks_in(word).is_some())
+          .collect();
+        assert_eq!(found, expected);
+      }
+
+      #[test]
+      fn anagrams_use_every_tile(words in vec(vec(0..4u8, 0..8), 0..50), rack in rack()) {
+        let dawg: FlatDawg = words.iter().collect();
+        let found: BTreeSet<_> = dawg.anagrams(&rack).collect();
+        let expected: BTreeSet<_> = dawg
+          .sub_anagrams(&rack)
+          .filter(|word| word.len() == rack.len())
+          .collect();
+        assert_eq!(found, expected);
+      }
+    }
+
+    #[test]
+    fn parse() {
+        let rack: Rack = "aab??".parse().unwrap();
+        assert_eq!(rack.count(0), 2);
+        assert_eq!(rack.count(1), 1);
+        assert_eq!(rack.count(2), 0);
+        assert_eq!(rack.blanks(), 2);
+        assert_eq!(rack.len(), 5);
+        assert_eq!(rack, Rack::new("aba", 2));
+
+        assert_eq!(
+            "ab*".parse::<Rack>(),
+            Err(LetterError { char: '*', idx: 2 })
+        );
+    }
+
+    #[test]
+    fn blanks_in() {
+        let rack = Rack::new("ab", 1);
+        assert_eq!(rack.blanks_in("ab"), Some(vec![]));
+        assert_eq!(rack.blanks_in("aba"), Some(vec![2]));
+        assert_eq!(rack.blanks_in("cab"), Some(vec![0]));
+        assert_eq!(rack.blanks_in("aa"), Some(vec![1]));
+        assert_eq!(rack.blanks_in("aab"), Some(vec![1]));
+        assert_eq!(rack.blanks_in("aaa"), None);
+        assert_eq!(Rack::default().blanks_in(""), Some(vec![]));
+    }
+}
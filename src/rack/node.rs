@@ -0,0 +1,27 @@
+use super::Rack;
+use crate::node::ReadNode;
+
+/// A node in a [`RackDawg`](super::RackDawg), after some tiles have been `used`
+pub struct RackNode<'a> {
+    pub(super) rack: &'a Rack,
+    pub(super) used: Rack,
+    pub(super) whole: bool,
+}
+
+impl ReadNode for RackNode<'_> {
+    type Idx = Rack;
+
+    fn is_end(&self) -> bool {
+        !self.whole || self.used.len() == self.rack.len()
+    }
+
+    fn has(&self, c: u8) -> bool {
+        self.used.count(c) < self.rack.count(c) || self.used.blanks < self.rack.blanks
+    }
+
+    fn get(&self, c: u8) -> Self::Idx {
+        let mut used = self.used.clone();
+        used.take(self.rack, c);
+        used
+    }
+}
@@ -0,0 +1,310 @@
+use super::convert::{IntoLetters, LetterError, TryIntoLetters};
+use serde::{Deserialize, Serialize};
+
+/// The number of letters in the [`Latin`] alphabet
+pub const LATIN_CHARS: usize = 26;
+
+/// The symbols of the [`Latin`] alphabet, in letter order
+const LATIN: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// A mapping between the symbols words are written with<br>
+/// and the `u8` letters that are stored in DAWGs.
+///
+/// Letters are numbered from `0` to `len() - 1`, so an alphabet<br>
+/// can be stored in [`ThinNode`](crate::ThinNode)s if it has at most<br>
+/// [`THIN_CHARS`](crate::THIN_CHARS) letters, or in a [`WideNode<N>`](crate::WideNode)<br>
+/// if it has at most `N` letters.
+pub trait Alphabet {
+    /// The number of letters in the alphabet
+    fn len(&self) -> usize;
+
+    /// Whether the alphabet has no letters
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parses the symbol at the start of `s`, returning its letter<br>
+    /// and the number of bytes the symbol takes up in `s`.
+    fn parse(&self, s: &str) -> Option<(u8, usize)>;
+
+    /// The symbol used to write a given letter
+    fn symbol(&self, c: u8) -> Option<&str>;
+
+    /// Spells out `s` using this alphabet, so that it can be passed<br>
+    /// to methods like [`ReadDawg::has`](crate::ReadDawg::has).
+    fn spell<'a>(&'a self, s: &'a str) -> Spelling<'a, Self>
+    where
+        Self: Sized,
+    {
+        Spelling {
+            alphabet: self,
+            word: s,
+        }
+    }
+
+    /// Translates from a word to letters.
+    ///
+    /// ## Panics
+    ///
+    /// If `s` contains a symbol that isn't in the alphabet.
+    fn to_letters(&self, s: &str) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        self.spell(s).letters().collect()
+    }
+
+    /// Translates from a word to letters, failing on the<br>
+    /// first character that doesn't start a symbol.
+    fn try_to_letters(&self, s: &str) -> Result<Vec<u8>, LetterError>
+    where
+        Self: Sized,
+    {
+        self.spell(s).try_letters().collect()
+    }
+
+    /// Translates from letters to a word.
+    ///
+    /// ## Panics
+    ///
+    /// If any of the letters are outside of the alphabet.
+    fn to_word(&self, letters: &[u8]) -> String {
+        letters
+            .iter()
+            .map(|&c| {
+                self.symbol(c)
+                    .unwrap_or_else(|| panic!("Letter {c} is not in the alphabet"))
+            })
+            .collect()
+    }
+}
+
+impl<A: Alphabet> Alphabet for &A {
+    fn len(&self) -> usize {
+        A::len(self)
+    }
+    fn parse(&self, s: &str) -> Option<(u8, usize)> {
+        A::parse(self, s)
+    }
+    fn symbol(&self, c: u8) -> Option<&str> {
+        A::symbol(self, c)
+    }
+}
+
+/// The lowercase latin alphabet, from `a` to `z`.
+///
+/// This is the alphabet used when `&str`s are used as words directly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Latin;
+
+impl Alphabet for Latin {
+    fn len(&self) -> usize {
+        LATIN_CHARS
+    }
+
+    fn parse(&self, s: &str) -> Option<(u8, usize)> {
+        let c = *s.as_bytes().first()?;
+        c.is_ascii_lowercase().then(|| (c - b'a', 1))
+    }
+
+    fn symbol(&self, c: u8) -> Option<&str> {
+        let c = c as usize;
+        LATIN.get(c..c + 1)
+    }
+}
+
+/// An alphabet of arbitrary symbols, where each symbol ("tile")<br>
+/// can be made up of multiple characters.
+///
+/// Letters are numbered in the order the tiles are given and<br>
+/// when parsing, the longest tile that matches is always used.
+///
+/// ```
+/// # use odawg::{Alphabet, Tiles};
+/// let spanish = Tiles::new([
+///     "a", "b", "c", "ch", "d", "e", "f", "g", "h", "i", "j", "l", "ll", "m",
+///     "n", "ñ", "o", "p", "q", "r", "rr", "s", "t", "u", "v", "x", "y", "z",
+/// ]);
+/// assert_eq!(spanish.len(), 28);
+///
+/// let letters = spanish.to_letters("llorar");
+/// assert_eq!(letters, [12, 16, 19, 0, 19]);
+/// assert_eq!(spanish.to_word(&letters), "llorar");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Tiles(Vec<String>);
+
+impl Tiles {
+    /// Creates an alphabet from its tiles, in letter order.
+    ///
+    /// ## Panics
+    ///
+    /// If any tile is empty or repeated, or there are more than 256 tiles.
+    pub fn new<S: Into<String>>(tiles: impl IntoIterator<Item = S>) -> Self {
+        let tiles: Vec<String> = tiles.into_iter().map(Into::into).collect();
+        assert!(tiles.len() <= 256, "At most 256 tiles can be used");
+        for (i, tile) in tiles.iter().enumerate() {
+            assert!(!tile.is_empty(), "Tile {i} is empty");
+            assert!(!tiles[..i].contains(tile), "Tile \"{tile}\" is repeated");
+        }
+        Self(tiles)
+    }
+}
+
+impl Alphabet for Tiles {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn parse(&self, s: &str) -> Option<(u8, usize)> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| s.starts_with(tile.as_str()))
+            .max_by_key(|(_, tile)| tile.len())
+            .map(|(c, tile)| (c as u8, tile.len()))
+    }
+
+    fn symbol(&self, c: u8) -> Option<&str> {
+        self.0.get(c as usize).map(String::as_str)
+    }
+}
+
+/// A word spelled out with a given alphabet, see [`Alphabet::spell`]
+#[derive(Clone, Copy, Debug)]
+pub struct Spelling<'a, A> {
+    alphabet: &'a A,
+    word: &'a str,
+}
+
+impl<A: Alphabet> TryIntoLetters for Spelling<'_, A> {
+    type TryLetterIter<'b>
+        = SpellIter<'b, A>
+    where
+        Self: 'b;
+    fn try_letters(&self) -> Self::TryLetterIter<'_> {
+        SpellIter {
+            alphabet: self.alphabet,
+            word: self.word,
+            idx: 0,
+        }
+    }
+}
+
+/// An iterator over the letters of a [`Spelling`],<br>
+/// yielding an error for each character not in the alphabet.
+pub struct SpellIter<'a, A> {
+    alphabet: &'a A,
+    word: &'a str,
+    idx: usize,
+}
+
+impl<A: Alphabet> Iterator for SpellIter<'_, A> {
+    type Item = Result<u8, LetterError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.word[self.idx..];
+        let char = rest.chars().next()?;
+        let Some((c, len)) = self.alphabet.parse(rest) else {
+            // skip just the unknown character, so later letters keep their place
+            let idx = self.idx;
+            self.idx += char.len_utf8();
+            return Some(Err(LetterError { char, idx }));
+        };
+
+        self.idx += len;
+        Some(Ok(c))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Alphabet, Latin, Tiles, LATIN_CHARS};
+    use crate::{
+        utils::{
+            convert::{from_word, into_word, LetterError, TryIntoLetters},
+            fixtures::{german, welsh},
+        },
+        FlatDawg, ReadDawg, ThinNode, WideNode,
+    };
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn latin_matches_convert(s in "[a-z]{0,20}") {
+        assert_eq!(Latin.to_letters(&s), from_word(&s));
+        assert_eq!(Latin.to_word(&from_word(&s)), into_word(from_word(&s)));
+      }
+
+      #[test]
+      fn tiles_round_trip(letters in prop::collection::vec(0..30u8, 0..20)) {
+        let alphabet = german();
+        let word = alphabet.to_word(&letters);
+        assert_eq!(alphabet.to_letters(&word), letters);
+      }
+    }
+
+    #[test]
+    fn latin_symbols() {
+        assert_eq!(Latin.len(), LATIN_CHARS);
+        assert_eq!(Latin.symbol(0), Some("a"));
+        assert_eq!(Latin.symbol(25), Some("z"));
+        assert_eq!(Latin.symbol(26), None);
+        assert_eq!(Latin.parse("cat"), Some((2, 1)));
+        assert_eq!(Latin.parse("Cat"), None);
+    }
+
+    #[test]
+    fn longest_tile() {
+        let alphabet = welsh();
+        assert_eq!(alphabet.to_letters("llwyd").len(), 4);
+        assert_eq!(alphabet.to_letters("cyngor").len(), 5);
+        assert_eq!(alphabet.to_word(&alphabet.to_letters("cyngor")), "cyngor");
+        assert_eq!(german().to_letters("straße").len(), 6);
+    }
+
+    #[test]
+    fn german_dawg() {
+        let alphabet = german();
+        let words = ["straße", "strasse", "übel", "öl", "zäh"];
+        let mut dawg: FlatDawg<WideNode<30>> =
+            words.iter().map(|word| alphabet.to_letters(word)).collect();
+        dawg.trim();
+        let dawg: FlatDawg<ThinNode> = dawg.try_into().unwrap();
+
+        assert_eq!(dawg.len(), words.len());
+        for word in words {
+            assert!(dawg.has(alphabet.spell(word)));
+        }
+        assert!(!dawg.has(alphabet.spell("strase")));
+
+        let mut found: Vec<_> = dawg.words().map(|word| alphabet.to_word(&word)).collect();
+        found.sort();
+        let mut words = words.map(String::from);
+        words.sort();
+        assert_eq!(found, words);
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_tile() {
+        Tiles::new(["a", "b", "a"]);
+    }
+
+    #[test]
+    fn try_unknown_symbol() {
+        let err = german().try_to_letters("garçon").unwrap_err();
+        assert_eq!(err, LetterError { char: 'ç', idx: 3 });
+        let letters: Vec<_> = german().spell("garçon").try_letters().collect();
+        assert_eq!(letters.len(), 6);
+        assert_eq!(letters[3], Err(LetterError { char: 'ç', idx: 3 }));
+        assert_eq!(letters[5], Ok(13));
+        assert_eq!(welsh().try_to_letters("quiz").unwrap_err().char, 'q');
+        assert_eq!(welsh().try_to_letters("cwtch"), Ok(vec![2, 27, 24, 3]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_symbol() {
+        german().to_letters("garçon");
+    }
+}
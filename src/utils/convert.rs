@@ -1,23 +1,70 @@
-use std::ops::Range;
+use super::LATIN_CHARS;
+use std::{fmt::Display, ops::Range};
 
 pub const ALPHA_START: u8 = b'a';
-pub const ALPHA_CHARS: u8 = 26;
+pub const ALPHA_CHARS: u8 = LATIN_CHARS as u8;
 const ALPHA_RANGE: Range<u8> = ALPHA_START..(ALPHA_START + ALPHA_CHARS);
 
+/// An error for when a word contains a character that can't be<br>
+/// represented as a letter, i.e. `'C'` or `'\''` for [`from_word`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LetterError {
+    /// The character that couldn't be converted
+    pub char: char,
+    /// The byte offset of the character in the word
+    pub idx: usize,
+}
+
+impl Display for LetterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Character {:?} at byte {} is not in the alphabet",
+            self.char, self.idx
+        )
+    }
+}
+
+impl std::error::Error for LetterError {}
+
+/// Translates from an lowercase alphabetic character to a `u8` letter,<br>
+/// returning `None` for characters outside of `a-z`.
+pub fn try_from_alpha(c: char) -> Option<u8> {
+    let v = u8::try_from(c).ok()?;
+    ALPHA_RANGE.contains(&v).then(|| v - ALPHA_START)
+}
+
 /// Translates from an lowercase alphabetic character to a `u8` letter
+///
+/// ## Panics
+///
+/// If `c` is outside of `a-z`.
 pub fn from_alpha(c: char) -> u8 {
-    debug_assert!(
-        ALPHA_RANGE.contains(&(c as u8)),
-        "Character '{c}' out the alphabetic range \"a-z\"",
-    );
-    c as u8 - ALPHA_START
+    try_from_alpha(c).unwrap_or_else(|| panic!("Character '{c}' out the alphabetic range \"a-z\""))
 }
 
 /// Translates from a word comprised of alphabetic characters to `u8` letters
+///
+/// ## Panics
+///
+/// If the word contains characters outside of `a-z`,<br>
+/// see [`try_from_word`] for a fallible version.
 pub fn from_word(s: impl AsRef<str>) -> Vec<u8> {
     s.as_ref().chars().map(from_alpha).collect()
 }
 
+/// Translates from a word comprised of alphabetic characters to `u8` letters,<br>
+/// failing on the first character outside of `a-z`.
+///
+/// ```
+/// # use odawg::{try_from_word, LetterError};
+/// assert_eq!(try_from_word("cat"), Ok(vec![2, 0, 19]));
+/// assert_eq!(try_from_word("can't"), Err(LetterError { char: '\'', idx: 3 }));
+/// ```
+pub fn try_from_word(s: impl AsRef<str>) -> Result<Vec<u8>, LetterError> {
+    s.as_ref().try_letters().collect()
+}
+
 /// Translates from a `u8` letter to a alphabetic character
 pub fn into_alpha(v: u8) -> char {
     debug_assert!(
@@ -32,8 +79,22 @@ pub fn into_word(v: impl AsRef<[u8]>) -> String {
     v.as_ref().iter().copied().map(into_alpha).collect()
 }
 
+/// A type that can be represented as a collection of items<br>
+/// that may fail to be converted into u8 letters.
+pub trait TryIntoLetters {
+    /// The iterator over letters, or the errors converting them
+    type TryLetterIter<'a>: Iterator<Item = Result<u8, LetterError>> + 'a
+    where
+        Self: 'a;
+    /// Converts `self` into letters, one item at a time
+    fn try_letters(&self) -> Self::TryLetterIter<'_>;
+}
+
 /// A type that can be represented as a
 /// collection of items convertible into u8 letters
+///
+/// This panics on items that can't be converted,<br>
+/// use [`TryIntoLetters`] to handle them instead.
 pub trait IntoLetters {
     type LetterIter<'a>: Iterator<Item = u8> + 'a
     where
@@ -41,59 +102,151 @@ pub trait IntoLetters {
     fn letters(&self) -> Self::LetterIter<'_>;
 }
 
-impl<I: IntoLetters> IntoLetters for &I {
-    type LetterIter<'b>
-        = I::LetterIter<'b>
+impl<T: TryIntoLetters> IntoLetters for T {
+    type LetterIter<'a>
+        = std::iter::Map<T::TryLetterIter<'a>, fn(Result<u8, LetterError>) -> u8>
     where
-        Self: 'b;
+        Self: 'a;
     fn letters(&self) -> Self::LetterIter<'_> {
-        I::letters(self)
+        self.try_letters().map(unwrap_letter)
     }
 }
 
-impl IntoLetters for &[u8] {
-    type LetterIter<'b>
-        = std::iter::Copied<std::slice::Iter<'b, u8>>
+/// Helper function to unwrap letters, panicking with the error message
+fn unwrap_letter(c: Result<u8, LetterError>) -> u8 {
+    c.unwrap_or_else(|err| panic!("{err}"))
+}
+
+impl<I: TryIntoLetters> TryIntoLetters for &I {
+    type TryLetterIter<'b>
+        = I::TryLetterIter<'b>
     where
         Self: 'b;
-    fn letters(&self) -> Self::LetterIter<'_> {
-        self.iter().copied()
+    fn try_letters(&self) -> Self::TryLetterIter<'_> {
+        I::try_letters(self)
+    }
+}
+
+impl TryIntoLetters for &[u8] {
+    type TryLetterIter<'b>
+        = std::iter::Map<
+        std::iter::Copied<std::slice::Iter<'b, u8>>,
+        fn(u8) -> Result<u8, LetterError>,
+    >
+    where
+        Self: 'b;
+    fn try_letters(&self) -> Self::TryLetterIter<'_> {
+        self.iter()
+            .copied()
+            .map(Ok as fn(u8) -> Result<u8, LetterError>)
     }
 }
-impl IntoLetters for Vec<u8> {
-    type LetterIter<'b>
-        = std::iter::Copied<std::slice::Iter<'b, u8>>
+impl TryIntoLetters for Vec<u8> {
+    type TryLetterIter<'b>
+        = std::iter::Map<
+        std::iter::Copied<std::slice::Iter<'b, u8>>,
+        fn(u8) -> Result<u8, LetterError>,
+    >
     where
         Self: 'b;
-    fn letters(&self) -> Self::LetterIter<'_> {
-        self.iter().copied()
+    fn try_letters(&self) -> Self::TryLetterIter<'_> {
+        self.iter()
+            .copied()
+            .map(Ok as fn(u8) -> Result<u8, LetterError>)
     }
 }
 
-impl IntoLetters for &str {
-    type LetterIter<'b>
+impl TryIntoLetters for &str {
+    type TryLetterIter<'b>
         = ConvertIter<'b>
     where
         Self: 'b;
-    fn letters(&self) -> Self::LetterIter<'_> {
-        ConvertIter(self.chars())
+    fn try_letters(&self) -> Self::TryLetterIter<'_> {
+        ConvertIter(self.char_indices())
     }
 }
-impl IntoLetters for String {
-    type LetterIter<'b>
+impl TryIntoLetters for String {
+    type TryLetterIter<'b>
         = ConvertIter<'b>
     where
         Self: 'b;
-    fn letters(&self) -> Self::LetterIter<'_> {
-        ConvertIter(self.chars())
+    fn try_letters(&self) -> Self::TryLetterIter<'_> {
+        ConvertIter(self.char_indices())
     }
 }
 
-pub struct ConvertIter<'a>(std::str::Chars<'a>);
+pub struct ConvertIter<'a>(std::str::CharIndices<'a>);
 
 impl Iterator for ConvertIter<'_> {
-    type Item = u8;
+    type Item = Result<u8, LetterError>;
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(from_alpha)
+        let (idx, c) = self.0.next()?;
+        Some(try_from_alpha(c).ok_or(LetterError { char: c, idx }))
+    }
+}
+
+/// Conversions for iterators of words, so that they can be collected<br>
+/// into DAWGs without panicking on words with invalid characters.
+///
+/// ```
+/// # use odawg::{FlatDawg, FromWords, LetterError, ReadDawg};
+/// let words = ["cat", "Dog", "can't"];
+///
+/// let dawg: Result<FlatDawg, _> = words.iter().try_words().collect();
+/// assert_eq!(dawg.unwrap_err(), LetterError { char: 'D', idx: 0 });
+///
+/// let dawg: FlatDawg = words.iter().valid_words().collect();
+/// assert_eq!(dawg.len(), 1);
+/// assert!(dawg.has("cat"));
+/// ```
+pub trait FromWords: Iterator + Sized
+where
+    Self::Item: AsRef<str>,
+{
+    /// Converts each word into letters, so that collecting<br>
+    /// into a `Result` reports the first invalid word.
+    fn try_words(self) -> impl Iterator<Item = Result<Vec<u8>, LetterError>> {
+        self.map(try_from_word)
+    }
+
+    /// Converts each word into letters, skipping any invalid words.
+    fn valid_words(self) -> impl Iterator<Item = Vec<u8>> {
+        self.filter_map(|word| try_from_word(word).ok())
+    }
+}
+
+impl<I: Iterator> FromWords for I where I::Item: AsRef<str> {}
+
+#[cfg(test)]
+mod test {
+    use super::{from_word, try_from_word, IntoLetters, LetterError, TryIntoLetters};
+    use proptest::prelude::*;
+
+    proptest! {
+      #[test]
+      fn try_matches_panicking(s in "[a-z]{0,20}") {
+        assert_eq!(try_from_word(&s), Ok(from_word(&s)));
+        assert_eq!(s.letters().collect::<Vec<_>>(), from_word(&s));
+      }
+
+      #[test]
+      fn reports_first_invalid(s in "[a-z]{0,10}", c in "[^a-z]", t in "\\PC{0,10}") {
+        let word = format!("{s}{c}{t}");
+        let char = c.chars().next().unwrap();
+        assert_eq!(try_from_word(&word), Err(LetterError { char, idx: s.len() }));
+      }
+    }
+
+    #[test]
+    fn multi_byte() {
+        let err = "naïve".try_letters().find_map(Result::err);
+        assert_eq!(err, Some(LetterError { char: 'ï', idx: 2 }));
+        assert!(try_from_word("über").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn upper_case() {
+        from_word("Cat");
     }
 }
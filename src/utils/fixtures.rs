@@ -0,0 +1,43 @@
+//! Fixtures shared between the tests of different modules
+
+use super::Tiles;
+use crate::{FlatDawg, ReadDawg, ThinNode, WideNode, WriteNode};
+
+/// The German alphabet, with umlauts and `ß` after `z`
+pub fn german() -> Tiles {
+    Tiles::new(('a'..='z').chain(['ä', 'ö', 'ü', 'ß']).map(String::from))
+}
+
+/// The Welsh alphabet, with digraphs such as `ch` and `ll`
+pub fn welsh() -> Tiles {
+    Tiles::new([
+        "a", "b", "c", "ch", "d", "dd", "e", "f", "ff", "g", "ng", "h", "i", "j", "l", "ll", "m",
+        "n", "o", "p", "ph", "r", "rh", "s", "t", "th", "u", "w", "y",
+    ])
+}
+
+/// A compact DAWG containing `words`
+pub fn thin_dawg(words: &[Vec<u8>]) -> FlatDawg<ThinNode> {
+    let mut dawg: FlatDawg<WideNode> = words.iter().collect();
+    dawg.trim();
+    dawg.try_into().expect("tries are always contiguous")
+}
+
+/// All the words in `dawg`, sorted
+pub fn sorted_words(dawg: &impl ReadDawg) -> Vec<Vec<u8>> {
+    let mut words: Vec<_> = dawg.words().collect();
+    words.sort();
+    words
+}
+
+/// A DAWG of every word of length `len` made from<br>
+/// the first 2 letters, with only `len + 1` nodes.
+pub fn binary_words(len: usize) -> FlatDawg {
+    let mut nodes = vec![WideNode::default(); len + 1];
+    for (i, node) in nodes.iter_mut().take(len).enumerate() {
+        *node.get_mut(0) = i + 1;
+        *node.get_mut(1) = i + 1;
+    }
+    *nodes[len].is_end_mut() = true;
+    FlatDawg(nodes)
+}
@@ -1,5 +1,7 @@
 mod merge;
 pub use merge::MergeIter;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod serde_array;
 #[cfg(test)]
 mod sorted;
@@ -7,5 +9,7 @@ mod sorted;
 pub use sorted::IsSorted;
 mod end_sort;
 pub use end_sort::EndSort;
+pub mod alphabet;
+pub use alphabet::{Alphabet, Latin, Spelling, Tiles, LATIN_CHARS};
 pub mod convert;
-pub use convert::{from_word, into_word};
+pub use convert::{from_word, into_word, try_from_word, FromWords, LetterError, TryIntoLetters};